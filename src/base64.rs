@@ -0,0 +1,360 @@
+use std::error::Error;
+use std::io::{self, Write};
+use std::ops::Range;
+use std::{fmt, mem};
+
+const STANDARD_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const URL_SAFE_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// A [base64](https://www.rfc-editor.org/rfc/rfc4648) alphabet.
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Hash, Default)]
+pub enum Base64Alphabet {
+    /// The standard alphabet using `+` and `/` ([RFC 4648 section 4](https://www.rfc-editor.org/rfc/rfc4648#section-4)).
+    #[default]
+    Standard,
+    /// The URL and filename safe alphabet using `-` and `_` ([RFC 4648 section 5](https://www.rfc-editor.org/rfc/rfc4648#section-5)).
+    UrlSafe,
+}
+
+impl Base64Alphabet {
+    const fn symbols(self) -> &'static [u8; 64] {
+        match self {
+            Self::Standard => STANDARD_ALPHABET,
+            Self::UrlSafe => URL_SAFE_ALPHABET,
+        }
+    }
+
+    fn value(self, symbol: u8) -> Option<u8> {
+        match symbol {
+            b'A'..=b'Z' => Some(symbol - b'A'),
+            b'a'..=b'z' => Some(symbol - b'a' + 26),
+            b'0'..=b'9' => Some(symbol - b'0' + 52),
+            b'+' if self == Self::Standard => Some(62),
+            b'/' if self == Self::Standard => Some(63),
+            b'-' if self == Self::UrlSafe => Some(62),
+            b'_' if self == Self::UrlSafe => Some(63),
+            _ => None,
+        }
+    }
+}
+
+/// Decodes a base64 string like the content of a [`JsonEvent::String`](crate::JsonEvent::String).
+///
+/// The padding with `=` is optional but, if present, must be complete.
+/// Whitespaces are not allowed.
+///
+/// ```
+/// use json_event_parser::{decode_base64, Base64Alphabet, JsonEvent, SliceJsonParser};
+///
+/// let mut parser = SliceJsonParser::new(br#"["aGVsbG8=", "aGVsbG8", "a?"]"#);
+/// assert_eq!(parser.parse_next()?, JsonEvent::StartArray);
+/// for _ in 0..2 {
+///     let JsonEvent::String(value) = parser.parse_next()? else {
+///         unreachable!()
+///     };
+///     assert_eq!(decode_base64(&value, Base64Alphabet::Standard)?, b"hello");
+/// }
+/// let JsonEvent::String(value) = parser.parse_next()? else {
+///     unreachable!()
+/// };
+/// let error = decode_base64(&value, Base64Alphabet::Standard).unwrap_err();
+/// assert_eq!(error.location(), 1..2);
+/// # std::io::Result::Ok(())
+/// ```
+pub fn decode_base64(input: &str, alphabet: Base64Alphabet) -> Result<Vec<u8>, Base64DecodeError> {
+    let mut output = Vec::with_capacity(input.len() / 4 * 3 + 2);
+    let mut decoder = Base64Decoder::new(alphabet);
+    decoder.decode_chunk(input, &mut output)?;
+    decoder.finish(&mut output)?;
+    Ok(output)
+}
+
+/// Incremental base64 decoder.
+///
+/// It allows to decode strings returned in chunks by parsers
+/// configured with [`with_string_chunk_size`](crate::ReaderJsonParser::with_string_chunk_size).
+/// It follows the same rules as [`decode_base64`].
+///
+/// ```
+/// use json_event_parser::{Base64Alphabet, Base64Decoder, JsonEvent, SliceJsonParser};
+///
+/// let mut parser = SliceJsonParser::new(br#""aGVsbG8gd29ybGQ""#).with_string_chunk_size(4);
+/// let mut decoder = Base64Decoder::new(Base64Alphabet::Standard);
+/// let mut output = Vec::new();
+/// loop {
+///     match parser.parse_next()? {
+///         JsonEvent::StringChunk(chunk) => decoder.decode_chunk(&chunk, &mut output)?,
+///         JsonEvent::String(end) => {
+///             decoder.decode_chunk(&end, &mut output)?;
+///             break;
+///         }
+///         _ => unreachable!(),
+///     }
+/// }
+/// decoder.finish(&mut output)?;
+/// assert_eq!(output, b"hello world");
+/// # std::io::Result::Ok(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[must_use]
+pub struct Base64Decoder {
+    alphabet: Base64Alphabet,
+    group: u32,
+    group_len: usize,
+    /// Number of padding characters still expected after the first one has been read
+    missing_padding: Option<usize>,
+    position: usize,
+}
+
+impl Base64Decoder {
+    #[inline]
+    pub const fn new(alphabet: Base64Alphabet) -> Self {
+        Self {
+            alphabet,
+            group: 0,
+            group_len: 0,
+            missing_padding: None,
+            position: 0,
+        }
+    }
+
+    /// Decodes a chunk of the string and appends the complete bytes to `output`.
+    pub fn decode_chunk(
+        &mut self,
+        chunk: &str,
+        output: &mut Vec<u8>,
+    ) -> Result<(), Base64DecodeError> {
+        for (i, symbol) in chunk.bytes().enumerate() {
+            let position = self.position + i;
+            if let Some(missing_padding) = &mut self.missing_padding {
+                if symbol != b'=' || *missing_padding == 0 {
+                    return Err(self.invalid_character_error(chunk, i, position));
+                }
+                *missing_padding -= 1;
+                continue;
+            }
+            if symbol == b'=' {
+                if self.group_len < 2 {
+                    return Err(Base64DecodeError {
+                        location: position..position + 1,
+                        message: "Unexpected base64 padding".into(),
+                    });
+                }
+                self.missing_padding = Some(3 - self.group_len);
+                self.flush_group(output);
+                continue;
+            }
+            let Some(value) = self.alphabet.value(symbol) else {
+                return Err(self.invalid_character_error(chunk, i, position));
+            };
+            self.group = (self.group << 6) | u32::from(value);
+            self.group_len += 1;
+            if self.group_len == 4 {
+                output.extend_from_slice(&self.group.to_be_bytes()[1..]);
+                self.group = 0;
+                self.group_len = 0;
+            }
+        }
+        self.position += chunk.len();
+        Ok(())
+    }
+
+    /// Checks the end of the string and appends the remaining bytes to `output`.
+    pub fn finish(mut self, output: &mut Vec<u8>) -> Result<(), Base64DecodeError> {
+        if self.missing_padding.is_some_and(|missing| missing > 0) {
+            return Err(Base64DecodeError {
+                location: self.position..self.position,
+                message: "Incomplete base64 padding".into(),
+            });
+        }
+        if self.group_len == 1 {
+            return Err(Base64DecodeError {
+                location: self.position - 1..self.position,
+                message: "Truncated base64 string".into(),
+            });
+        }
+        self.flush_group(output);
+        Ok(())
+    }
+
+    /// Writes the bytes of an incomplete group
+    fn flush_group(&mut self, output: &mut Vec<u8>) {
+        let group_len = mem::take(&mut self.group_len);
+        if group_len >= 2 {
+            let bytes = (mem::take(&mut self.group) << (6 * (4 - group_len))).to_be_bytes();
+            output.extend_from_slice(&bytes[1..group_len]);
+        }
+    }
+
+    fn invalid_character_error(
+        &self,
+        chunk: &str,
+        index: usize,
+        position: usize,
+    ) -> Base64DecodeError {
+        let c = chunk[index..]
+            .chars()
+            .next()
+            .unwrap_or(char::REPLACEMENT_CHARACTER);
+        Base64DecodeError {
+            location: position..position + c.len_utf8(),
+            message: if self.missing_padding.is_some() {
+                format!("Unexpected character {c:?} after base64 padding")
+            } else {
+                format!("Invalid base64 character {c:?}")
+            },
+        }
+    }
+}
+
+/// An error raised when decoding an invalid base64 string.
+#[derive(Debug)]
+pub struct Base64DecodeError {
+    location: Range<usize>,
+    message: String,
+}
+
+impl Base64DecodeError {
+    /// The byte range of the error inside of the decoded string.
+    #[inline]
+    pub fn location(&self) -> Range<usize> {
+        self.location.clone()
+    }
+
+    /// The error message.
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for Base64DecodeError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Base64 decoding error between bytes {} and {}: {}",
+            self.location.start, self.location.end, self.message
+        )
+    }
+}
+
+impl Error for Base64DecodeError {}
+
+impl From<Base64DecodeError> for io::Error {
+    #[inline]
+    fn from(error: Base64DecodeError) -> Self {
+        Self::new(io::ErrorKind::InvalidData, error)
+    }
+}
+
+/// Incremental base64 encoder.
+///
+/// Bytes that do not fill a full group of 3 bytes are kept until the next chunk or the end of the encoding.
+pub(crate) struct Base64Encoder {
+    alphabet: &'static [u8; 64],
+    with_padding: bool,
+    pending: [u8; 2],
+    pending_len: usize,
+}
+
+impl Base64Encoder {
+    pub(crate) const fn new() -> Self {
+        Self {
+            alphabet: STANDARD_ALPHABET,
+            with_padding: true,
+            pending: [0; 2],
+            pending_len: 0,
+        }
+    }
+
+    /// Drops the pending bytes of an unfinished encoding
+    pub(crate) fn reset(&mut self) {
+        self.pending_len = 0;
+    }
+
+    pub(crate) fn set_alphabet(&mut self, alphabet: Base64Alphabet) {
+        self.alphabet = alphabet.symbols();
+    }
+
+    pub(crate) fn set_padding(&mut self, with_padding: bool) {
+        self.with_padding = with_padding;
+    }
+
+    /// Encodes `input` and writes the complete groups.
+    pub(crate) fn encode_chunk(
+        &mut self,
+        mut input: &[u8],
+        mut write: impl Write,
+    ) -> io::Result<()> {
+        if self.pending_len > 0 {
+            let missing = 3 - self.pending_len;
+            if input.len() < missing {
+                self.pending[self.pending_len..self.pending_len + input.len()]
+                    .copy_from_slice(input);
+                self.pending_len += input.len();
+                return Ok(());
+            }
+            let mut group = [0; 3];
+            group[..self.pending_len].copy_from_slice(&self.pending[..self.pending_len]);
+            group[self.pending_len..].copy_from_slice(&input[..missing]);
+            input = &input[missing..];
+            self.pending_len = 0;
+            write.write_all(&self.encode_group(group))?;
+        }
+        let mut buffer = [0; 1024];
+        let mut groups = input.chunks_exact(3);
+        loop {
+            let mut buffer_len = 0;
+            for group in groups.by_ref().take(buffer.len() / 4) {
+                buffer[buffer_len..buffer_len + 4]
+                    .copy_from_slice(&self.encode_group([group[0], group[1], group[2]]));
+                buffer_len += 4;
+            }
+            if buffer_len == 0 {
+                break;
+            }
+            write.write_all(&buffer[..buffer_len])?;
+        }
+        let remainder = groups.remainder();
+        self.pending[..remainder.len()].copy_from_slice(remainder);
+        self.pending_len = remainder.len();
+        Ok(())
+    }
+
+    /// Writes the pending bytes with padding if enabled and resets the encoder.
+    pub(crate) fn finish(&mut self, mut write: impl Write) -> io::Result<()> {
+        let pending_len = self.pending_len;
+        self.pending_len = 0;
+        if pending_len == 0 {
+            return Ok(());
+        }
+        let mut group = [0; 3];
+        group[..pending_len].copy_from_slice(&self.pending[..pending_len]);
+        let mut encoded = self.encode_group(group);
+        if self.with_padding {
+            encoded[pending_len + 1..].fill(b'=');
+            write.write_all(&encoded)
+        } else {
+            write.write_all(&encoded[..pending_len + 1])
+        }
+    }
+
+    fn encode_group(&self, group: [u8; 3]) -> [u8; 4] {
+        let value = (u32::from(group[0]) << 16) | (u32::from(group[1]) << 8) | u32::from(group[2]);
+        [
+            self.alphabet[(value >> 18) as usize & 0x3F],
+            self.alphabet[(value >> 12) as usize & 0x3F],
+            self.alphabet[(value >> 6) as usize & 0x3F],
+            self.alphabet[value as usize & 0x3F],
+        ]
+    }
+}
+
+impl Default for Base64Encoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,558 @@
+use crate::read::{max_buffer_size_error, MAX_BUFFER_SIZE};
+use crate::{
+    JsonEvent, JsonLimits, JsonParseError, JsonSyntaxError, LoneSurrogate, LowLevelJsonParser,
+    LowLevelJsonParserResult, TextPosition,
+};
+use std::cmp::{max, min};
+use std::io::BufRead;
+use std::mem;
+#[cfg(feature = "async-tokio")]
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+
+/// Parses a JSON file from a [`BufRead`] implementation.
+///
+/// Unlike [`ReaderJsonParser`](crate::ReaderJsonParser), the events are parsed directly from the buffer of the input
+/// without copying it into an internal buffer.
+/// Only the tokens spanning several refills of the input buffer are copied.
+///
+/// ```
+/// use json_event_parser::{BufReaderJsonParser, JsonEvent};
+/// use std::io::BufReader;
+///
+/// let mut reader = BufReaderJsonParser::new(BufReader::new(b"{\"foo\": 1}".as_slice()));
+/// assert_eq!(reader.parse_next()?, JsonEvent::StartObject);
+/// assert_eq!(reader.parse_next()?, JsonEvent::ObjectKey("foo".into()));
+/// assert_eq!(reader.parse_next()?, JsonEvent::Number("1".into()));
+/// assert_eq!(reader.parse_next()?, JsonEvent::EndObject);
+/// assert_eq!(reader.parse_next()?, JsonEvent::Eof);
+/// # std::io::Result::Ok(())
+/// ```
+pub struct BufReaderJsonParser<R: BufRead> {
+    read: R,
+    state: BufReadParserState,
+}
+
+impl<R: BufRead> BufReaderJsonParser<R> {
+    #[inline]
+    pub const fn new(read: R) -> Self {
+        Self {
+            read,
+            state: BufReadParserState::new(),
+        }
+    }
+
+    /// Sets the max size in bytes of the internal buffer keeping the tokens spanning several refills of the input buffer.
+    #[inline]
+    pub fn with_max_buffer_size(mut self, size: usize) -> Self {
+        self.state.max_buffer_size = size;
+        self
+    }
+
+    /// Returns strings and object keys longer than `size` bytes in chunks.
+    ///
+    /// It allows to read strings bigger than the [maximal buffer size](Self::with_max_buffer_size) that must be greater than `size`.
+    /// See [`LowLevelJsonParser::with_string_chunk_size`] for details.
+    #[inline]
+    pub fn with_string_chunk_size(mut self, size: usize) -> Self {
+        self.state.parser = self.state.parser.with_string_chunk_size(size);
+        self
+    }
+
+    /// Maximal allowed number of nested object and array openings.
+    ///
+    /// See [`LowLevelJsonParser::with_max_stack_size`] for details.
+    #[inline]
+    pub fn with_max_stack_size(mut self, size: usize) -> Self {
+        self.state.parser = self.state.parser.with_max_stack_size(size);
+        self
+    }
+
+    /// Returns an error if an object contains twice the same key.
+    ///
+    /// See [`LowLevelJsonParser::with_duplicate_object_key_detection`] for details.
+    #[inline]
+    pub fn with_duplicate_object_key_detection(mut self) -> Self {
+        self.state.parser = self.state.parser.with_duplicate_object_key_detection();
+        self
+    }
+
+    /// Maximal total size in bytes of the object keys kept in memory for duplicate key detection.
+    ///
+    /// See [`LowLevelJsonParser::with_max_tracked_object_keys_size`] for details.
+    #[inline]
+    pub fn with_max_tracked_object_keys_size(mut self, size: usize) -> Self {
+        self.state.parser = self.state.parser.with_max_tracked_object_keys_size(size);
+        self
+    }
+
+    /// Validates that the input follows the [I-JSON](https://www.rfc-editor.org/rfc/rfc7493) profile.
+    ///
+    /// See [`LowLevelJsonParser::with_i_json_validation`] for details.
+    #[inline]
+    pub fn with_i_json_validation(mut self) -> Self {
+        self.state.parser = self.state.parser.with_i_json_validation();
+        self
+    }
+
+    /// Sets limits on the size of the parsed input to protect against malicious documents.
+    ///
+    /// See [`LowLevelJsonParser::with_limits`] for details.
+    #[inline]
+    pub fn with_limits(mut self, limits: JsonLimits) -> Self {
+        self.state.parser = self.state.parser.with_limits(limits);
+        self
+    }
+
+    /// Allows escaped lone UTF-16 surrogates like `"\uD888"` in strings and object keys.
+    ///
+    /// See [`LowLevelJsonParser::with_lone_surrogate_preservation`] for details.
+    #[inline]
+    pub fn with_lone_surrogate_preservation(mut self) -> Self {
+        self.state.parser = self.state.parser.with_lone_surrogate_preservation();
+        self
+    }
+
+    /// The lone surrogates replaced by U+FFFD in the string or object key returned by the last call to `parse_next`.
+    ///
+    /// See [`LowLevelJsonParser::lone_surrogates`] for details.
+    #[inline]
+    pub fn lone_surrogates(&self) -> &[LoneSurrogate] {
+        self.state.parser.lone_surrogates()
+    }
+
+    /// Replaces invalid UTF-8 sequences and lone surrogate escapes in strings and object keys by U+FFFD instead of returning an error.
+    ///
+    /// See [`LowLevelJsonParser::with_lossy_decoding`] for details.
+    #[inline]
+    pub fn with_lossy_decoding(mut self) -> Self {
+        self.state.parser = self.state.parser.with_lossy_decoding();
+        self
+    }
+
+    /// The invalid sequences replaced by U+FFFD in the string or object key returned by the last call to `parse_next`.
+    ///
+    /// See [`LowLevelJsonParser::decoding_warnings`] for details.
+    #[inline]
+    pub fn decoding_warnings(&self) -> &[JsonSyntaxError] {
+        self.state.parser.decoding_warnings()
+    }
+
+    /// Collects the recoverable syntax errors instead of returning them and repairs the returned events.
+    ///
+    /// See [`LowLevelJsonParser::with_error_recovery`] for details.
+    #[inline]
+    pub fn with_error_recovery(mut self) -> Self {
+        self.state.parser = self.state.parser.with_error_recovery();
+        self
+    }
+
+    /// Maximal number of errors collected in [error recovery mode](Self::with_error_recovery).
+    ///
+    /// See [`LowLevelJsonParser::with_max_recovered_errors`] for details.
+    #[inline]
+    pub fn with_max_recovered_errors(mut self, count: usize) -> Self {
+        self.state.parser = self.state.parser.with_max_recovered_errors(count);
+        self
+    }
+
+    /// The errors collected since the beginning of the parsing in [error recovery mode](Self::with_error_recovery).
+    ///
+    /// See [`LowLevelJsonParser::diagnostics`] for details.
+    #[inline]
+    pub fn diagnostics(&self) -> &[JsonSyntaxError] {
+        self.state.parser.diagnostics()
+    }
+
+    /// The position in the input after the bytes consumed so far.
+    ///
+    /// See [`LowLevelJsonParser::position`] for details.
+    #[inline]
+    pub fn position(&self) -> TextPosition {
+        self.state.parser.position()
+    }
+
+    /// Resets the parser to parse a new document from the same input.
+    ///
+    /// The bytes of a token spanning several refills of the input buffer already consumed from the input are discarded.
+    /// See [`ReaderJsonParser::reset`](crate::ReaderJsonParser::reset) for details.
+    pub fn reset(&mut self) {
+        self.read.consume(self.state.take_pending_consume());
+        self.state.reset();
+    }
+
+    /// Resets the parser to parse a new document from `read` and returns the previous input.
+    ///
+    /// The bytes of the previous input used by the last returned event are consumed before it is returned.
+    /// See [`reset`](Self::reset) for details.
+    pub fn reset_with_reader(&mut self, read: R) -> R {
+        self.reset();
+        mem::replace(&mut self.read, read)
+    }
+
+    #[inline]
+    pub fn parse_next(&mut self) -> Result<JsonEvent<'_>, JsonParseError> {
+        self.read.consume(self.state.take_pending_consume());
+        loop {
+            let data = if self.state.parser.reads_tokens() {
+                #[allow(unsafe_code)]
+                unsafe {
+                    let data_ptr: *const [u8] = self.read.fill_buf()?;
+                    &*data_ptr
+                } // SAFETY: Borrow checker workaround https://github.com/rust-lang/rust/issues/70255
+            } else {
+                &[]
+            };
+            let (event, consumed_bytes) = self.state.parse_step(data);
+            if let Some(event) = event {
+                // The input is not touched while the event might borrow its buffer
+                return event;
+            }
+            self.read.consume(consumed_bytes);
+        }
+    }
+
+    /// Returns the next event without consuming it.
+    ///
+    /// See [`ReaderJsonParser::peek_next`](crate::ReaderJsonParser::peek_next) for details.
+    pub fn peek_next(&mut self) -> Result<&JsonEvent<'static>, JsonParseError> {
+        if self.state.parser.buffered_event.is_none() {
+            let event = self.parse_next()?.into_owned();
+            self.state.parser.buffer_peeked_event(event);
+        }
+        Ok(self.state.parser.buffered_event.as_ref().unwrap())
+    }
+
+    /// The maximal number of nested arrays and objects if set with [`with_max_stack_size`](Self::with_max_stack_size).
+    #[inline]
+    pub(crate) fn max_stack_size(&self) -> Option<usize> {
+        self.state.parser.max_stack_size()
+    }
+}
+
+/// Parses a JSON file from an [`AsyncBufRead`] implementation.
+///
+/// It is the asynchronous counterpart of [`BufReaderJsonParser`].
+///
+/// ```
+/// use json_event_parser::{JsonEvent, TokioAsyncBufReaderJsonParser};
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> ::std::io::Result<()> {
+/// let mut reader = TokioAsyncBufReaderJsonParser::new(b"{\"foo\": 1}".as_slice());
+/// assert_eq!(reader.parse_next().await?, JsonEvent::StartObject);
+/// assert_eq!(
+///     reader.parse_next().await?,
+///     JsonEvent::ObjectKey("foo".into())
+/// );
+/// assert_eq!(reader.parse_next().await?, JsonEvent::Number("1".into()));
+/// assert_eq!(reader.parse_next().await?, JsonEvent::EndObject);
+/// assert_eq!(reader.parse_next().await?, JsonEvent::Eof);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "async-tokio")]
+pub struct TokioAsyncBufReaderJsonParser<R: AsyncBufRead + Unpin> {
+    read: R,
+    state: BufReadParserState,
+}
+
+#[cfg(feature = "async-tokio")]
+impl<R: AsyncBufRead + Unpin> TokioAsyncBufReaderJsonParser<R> {
+    #[inline]
+    pub const fn new(read: R) -> Self {
+        Self {
+            read,
+            state: BufReadParserState::new(),
+        }
+    }
+
+    /// Sets the max size in bytes of the internal buffer keeping the tokens spanning several refills of the input buffer.
+    #[inline]
+    pub fn with_max_buffer_size(mut self, size: usize) -> Self {
+        self.state.max_buffer_size = size;
+        self
+    }
+
+    /// Returns strings and object keys longer than `size` bytes in chunks.
+    ///
+    /// See [`LowLevelJsonParser::with_string_chunk_size`] for details.
+    #[inline]
+    pub fn with_string_chunk_size(mut self, size: usize) -> Self {
+        self.state.parser = self.state.parser.with_string_chunk_size(size);
+        self
+    }
+
+    /// Maximal allowed number of nested object and array openings.
+    ///
+    /// See [`LowLevelJsonParser::with_max_stack_size`] for details.
+    #[inline]
+    pub fn with_max_stack_size(mut self, size: usize) -> Self {
+        self.state.parser = self.state.parser.with_max_stack_size(size);
+        self
+    }
+
+    /// Returns an error if an object contains twice the same key.
+    ///
+    /// See [`LowLevelJsonParser::with_duplicate_object_key_detection`] for details.
+    #[inline]
+    pub fn with_duplicate_object_key_detection(mut self) -> Self {
+        self.state.parser = self.state.parser.with_duplicate_object_key_detection();
+        self
+    }
+
+    /// Maximal total size in bytes of the object keys kept in memory for duplicate key detection.
+    ///
+    /// See [`LowLevelJsonParser::with_max_tracked_object_keys_size`] for details.
+    #[inline]
+    pub fn with_max_tracked_object_keys_size(mut self, size: usize) -> Self {
+        self.state.parser = self.state.parser.with_max_tracked_object_keys_size(size);
+        self
+    }
+
+    /// Validates that the input follows the [I-JSON](https://www.rfc-editor.org/rfc/rfc7493) profile.
+    ///
+    /// See [`LowLevelJsonParser::with_i_json_validation`] for details.
+    #[inline]
+    pub fn with_i_json_validation(mut self) -> Self {
+        self.state.parser = self.state.parser.with_i_json_validation();
+        self
+    }
+
+    /// Sets limits on the size of the parsed input to protect against malicious documents.
+    ///
+    /// See [`LowLevelJsonParser::with_limits`] for details.
+    #[inline]
+    pub fn with_limits(mut self, limits: JsonLimits) -> Self {
+        self.state.parser = self.state.parser.with_limits(limits);
+        self
+    }
+
+    /// Allows escaped lone UTF-16 surrogates like `"\uD888"` in strings and object keys.
+    ///
+    /// See [`LowLevelJsonParser::with_lone_surrogate_preservation`] for details.
+    #[inline]
+    pub fn with_lone_surrogate_preservation(mut self) -> Self {
+        self.state.parser = self.state.parser.with_lone_surrogate_preservation();
+        self
+    }
+
+    /// The lone surrogates replaced by U+FFFD in the string or object key returned by the last call to `parse_next`.
+    ///
+    /// See [`LowLevelJsonParser::lone_surrogates`] for details.
+    #[inline]
+    pub fn lone_surrogates(&self) -> &[LoneSurrogate] {
+        self.state.parser.lone_surrogates()
+    }
+
+    /// Replaces invalid UTF-8 sequences and lone surrogate escapes in strings and object keys by U+FFFD instead of returning an error.
+    ///
+    /// See [`LowLevelJsonParser::with_lossy_decoding`] for details.
+    #[inline]
+    pub fn with_lossy_decoding(mut self) -> Self {
+        self.state.parser = self.state.parser.with_lossy_decoding();
+        self
+    }
+
+    /// The invalid sequences replaced by U+FFFD in the string or object key returned by the last call to `parse_next`.
+    ///
+    /// See [`LowLevelJsonParser::decoding_warnings`] for details.
+    #[inline]
+    pub fn decoding_warnings(&self) -> &[JsonSyntaxError] {
+        self.state.parser.decoding_warnings()
+    }
+
+    /// Collects the recoverable syntax errors instead of returning them and repairs the returned events.
+    ///
+    /// See [`LowLevelJsonParser::with_error_recovery`] for details.
+    #[inline]
+    pub fn with_error_recovery(mut self) -> Self {
+        self.state.parser = self.state.parser.with_error_recovery();
+        self
+    }
+
+    /// Maximal number of errors collected in [error recovery mode](Self::with_error_recovery).
+    ///
+    /// See [`LowLevelJsonParser::with_max_recovered_errors`] for details.
+    #[inline]
+    pub fn with_max_recovered_errors(mut self, count: usize) -> Self {
+        self.state.parser = self.state.parser.with_max_recovered_errors(count);
+        self
+    }
+
+    /// The errors collected since the beginning of the parsing in [error recovery mode](Self::with_error_recovery).
+    ///
+    /// See [`LowLevelJsonParser::diagnostics`] for details.
+    #[inline]
+    pub fn diagnostics(&self) -> &[JsonSyntaxError] {
+        self.state.parser.diagnostics()
+    }
+
+    /// The position in the input after the bytes consumed so far.
+    ///
+    /// See [`LowLevelJsonParser::position`] for details.
+    #[inline]
+    pub fn position(&self) -> TextPosition {
+        self.state.parser.position()
+    }
+
+    /// Resets the parser to parse a new document from the same input.
+    ///
+    /// See [`BufReaderJsonParser::reset`] for details.
+    pub fn reset(&mut self) {
+        self.read.consume(self.state.take_pending_consume());
+        self.state.reset();
+    }
+
+    /// Resets the parser to parse a new document from `read` and returns the previous input.
+    ///
+    /// See [`BufReaderJsonParser::reset_with_reader`] for details.
+    pub fn reset_with_reader(&mut self, read: R) -> R {
+        self.reset();
+        mem::replace(&mut self.read, read)
+    }
+
+    pub async fn parse_next(&mut self) -> Result<JsonEvent<'_>, JsonParseError> {
+        self.read.consume(self.state.take_pending_consume());
+        loop {
+            let data = if self.state.parser.reads_tokens() {
+                #[allow(unsafe_code)]
+                unsafe {
+                    let data_ptr: *const [u8] = self.read.fill_buf().await?;
+                    &*data_ptr
+                } // SAFETY: Borrow checker workaround https://github.com/rust-lang/rust/issues/70255
+            } else {
+                &[]
+            };
+            let (event, consumed_bytes) = self.state.parse_step(data);
+            if let Some(event) = event {
+                // The input is not touched while the event might borrow its buffer
+                return event;
+            }
+            self.read.consume(consumed_bytes);
+        }
+    }
+
+    /// Returns the next event without consuming it.
+    ///
+    /// See [`ReaderJsonParser::peek_next`](crate::ReaderJsonParser::peek_next) for details.
+    pub async fn peek_next(&mut self) -> Result<&JsonEvent<'static>, JsonParseError> {
+        if self.state.parser.buffered_event.is_none() {
+            let event = self.parse_next().await?.into_owned();
+            self.state.parser.buffer_peeked_event(event);
+        }
+        Ok(self.state.parser.buffered_event.as_ref().unwrap())
+    }
+}
+
+/// Parsing state shared by [`BufReaderJsonParser`] and [`TokioAsyncBufReaderJsonParser`]
+struct BufReadParserState {
+    parser: LowLevelJsonParser,
+    /// The beginning of a token spanning several refills of the input buffer
+    token_buffer: Vec<u8>,
+    /// End of the bytes of `token_buffer` used by the last returned event
+    token_buffer_start: usize,
+    /// End of the bytes of `token_buffer` consumed from the input, the following ones are still in the input buffer
+    token_buffer_end: usize,
+    max_buffer_size: usize,
+    /// Bytes of the input buffer used by the last returned event, consumed before reading the input again
+    pending_consume: usize,
+}
+
+impl BufReadParserState {
+    const fn new() -> Self {
+        Self {
+            parser: LowLevelJsonParser::new(),
+            token_buffer: Vec::new(),
+            token_buffer_start: 0,
+            token_buffer_end: 0,
+            max_buffer_size: MAX_BUFFER_SIZE,
+            pending_consume: 0,
+        }
+    }
+
+    fn take_pending_consume(&mut self) -> usize {
+        mem::take(&mut self.pending_consume)
+    }
+
+    fn reset(&mut self) {
+        self.parser.reset();
+        self.token_buffer.clear();
+        self.token_buffer_start = 0;
+        self.token_buffer_end = 0;
+        self.pending_consume = 0;
+    }
+
+    /// Parses the next event from `data`, the current content of the input buffer.
+    ///
+    /// Returns the event if any or else the number of bytes to consume from the input buffer.
+    /// If an event is returned, the bytes of the input buffer it used are only consumed by [`take_pending_consume`](Self::take_pending_consume)
+    /// to keep valid the event that might borrow `data`.
+    fn parse_step<'a>(
+        &mut self,
+        data: &'a [u8],
+    ) -> (Option<Result<JsonEvent<'a>, JsonParseError>>, usize) {
+        if self.token_buffer_start > 0 || self.token_buffer_end < self.token_buffer.len() {
+            // We drop the bytes used by the last event and the ones still in the input buffer
+            self.token_buffer.truncate(self.token_buffer_end);
+            self.token_buffer.drain(..self.token_buffer_start);
+            self.token_buffer_start = 0;
+            self.token_buffer_end = self.token_buffer.len();
+        }
+        let is_ending = data.is_empty();
+
+        if self.token_buffer.is_empty() {
+            // We parse directly from the input buffer
+            let LowLevelJsonParserResult {
+                event,
+                consumed_bytes,
+            } = self.parser.parse_next(data, is_ending);
+            if let Some(event) = event {
+                self.pending_consume = consumed_bytes;
+                return (Some(event.map_err(JsonParseError::from)), 0);
+            }
+            // The end of the input buffer is the beginning of a token, we copy it
+            let rest = &data[consumed_bytes..];
+            if rest.len() > self.max_buffer_size {
+                return (
+                    Some(Err(max_buffer_size_error(self.max_buffer_size).into())),
+                    0,
+                );
+            }
+            self.token_buffer.extend_from_slice(rest);
+            self.token_buffer_end = self.token_buffer.len();
+            return (None, data.len());
+        }
+
+        // We append the input buffer to the beginning of the token
+        let previous_len = self.token_buffer.len();
+        if previous_len >= self.max_buffer_size {
+            return (
+                Some(Err(max_buffer_size_error(self.max_buffer_size).into())),
+                0,
+            );
+        }
+        let appended = min(data.len(), self.max_buffer_size - previous_len);
+        self.token_buffer.extend_from_slice(&data[..appended]);
+        let LowLevelJsonParserResult {
+            event,
+            consumed_bytes,
+        } = self.parser.parse_next(
+            #[allow(unsafe_code)]
+            unsafe {
+                let token_buffer_ptr: *const [u8] = self.token_buffer.as_slice();
+                &*token_buffer_ptr
+            }, // SAFETY: Borrow checker workaround https://github.com/rust-lang/rust/issues/70255
+            is_ending,
+        );
+        if let Some(event) = event {
+            // Only the bytes of the input buffer used by the event are consumed
+            self.token_buffer_start = consumed_bytes;
+            self.token_buffer_end = max(previous_len, consumed_bytes);
+            self.pending_consume = consumed_bytes.saturating_sub(previous_len);
+            return (Some(event.map_err(JsonParseError::from)), 0);
+        }
+        self.token_buffer.drain(..consumed_bytes);
+        self.token_buffer_end = self.token_buffer.len();
+        (None, appended)
+    }
+}
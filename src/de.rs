@@ -0,0 +1,765 @@
+use crate::{
+    JsonEvent, JsonEventSource, JsonParseError, JsonSyntaxError, ReaderJsonParser, SliceJsonParser,
+};
+use serde::de::value::{BorrowedStrDeserializer, StrDeserializer, StringDeserializer};
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, EnumAccess, MapAccess, SeqAccess, VariantAccess,
+    Visitor,
+};
+use serde::{forward_to_deserialize_any, Deserialize};
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read};
+
+const MAX_RECURSION_DEPTH: u8 = 128;
+
+impl<'a> SliceJsonParser<'a> {
+    /// Deserializes the next JSON value using [serde](https://serde.rs/).
+    ///
+    /// Only the events of the next value are consumed, so it can be used in the middle of a document.
+    /// Strings are borrowed from the input when possible.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, SliceJsonParser};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize, PartialEq, Debug)]
+    /// struct Point<'a> {
+    ///     name: &'a str,
+    ///     x: i64,
+    ///     y: i64,
+    /// }
+    ///
+    /// let mut reader = SliceJsonParser::new(b"[{\"name\": \"a\", \"x\": 1, \"y\": 2}]");
+    /// assert_eq!(reader.parse_next()?, JsonEvent::StartArray);
+    /// assert_eq!(
+    ///     reader.deserialize_next::<Point<'_>>()?,
+    ///     Point {
+    ///         name: "a",
+    ///         x: 1,
+    ///         y: 2
+    ///     }
+    /// );
+    /// assert_eq!(reader.parse_next()?, JsonEvent::EndArray);
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn deserialize_next<T: Deserialize<'a>>(&mut self) -> Result<T, JsonDeserializeError> {
+        T::deserialize(&mut Deserializer::new(SliceRead { parser: self }))
+    }
+}
+
+impl<R: Read> ReaderJsonParser<R> {
+    /// Deserializes the next JSON value using [serde](https://serde.rs/).
+    ///
+    /// Only the events of the next value are consumed, so it can be used in the middle of a document.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, ReaderJsonParser};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize, PartialEq, Debug)]
+    /// struct Point {
+    ///     x: i64,
+    ///     y: i64,
+    /// }
+    ///
+    /// let mut reader =
+    ///     ReaderJsonParser::new(b"[{\"x\": 1, \"y\": 2}, {\"x\": 3, \"y\": 4}]".as_slice());
+    /// assert_eq!(reader.parse_next()?, JsonEvent::StartArray);
+    /// let mut points = Vec::new();
+    /// while reader.peek_next()? != &JsonEvent::EndArray {
+    ///     points.push(reader.deserialize_next::<Point>()?);
+    /// }
+    /// assert_eq!(points, [Point { x: 1, y: 2 }, Point { x: 3, y: 4 }]);
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn deserialize_next<T: DeserializeOwned>(&mut self) -> Result<T, JsonDeserializeError> {
+        T::deserialize(&mut Deserializer::new(SourceRead { source: self }))
+    }
+}
+
+/// An error raised during [serde](https://serde.rs/) deserialization.
+#[derive(Debug)]
+pub enum JsonDeserializeError {
+    /// The input is not valid JSON or could not be read.
+    Parse(JsonParseError),
+    /// The JSON value does not match the expected data structure.
+    Data(String),
+}
+
+impl fmt::Display for JsonDeserializeError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(e) => e.fmt(f),
+            Self::Data(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for JsonDeserializeError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Parse(e) => Some(e),
+            Self::Data(_) => None,
+        }
+    }
+}
+
+impl de::Error for JsonDeserializeError {
+    #[inline]
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self::Data(msg.to_string())
+    }
+}
+
+impl From<JsonParseError> for JsonDeserializeError {
+    #[inline]
+    fn from(error: JsonParseError) -> Self {
+        Self::Parse(error)
+    }
+}
+
+impl From<JsonSyntaxError> for JsonDeserializeError {
+    #[inline]
+    fn from(error: JsonSyntaxError) -> Self {
+        Self::Parse(error.into())
+    }
+}
+
+impl From<JsonDeserializeError> for io::Error {
+    #[inline]
+    fn from(error: JsonDeserializeError) -> Self {
+        match error {
+            JsonDeserializeError::Parse(e) => e.into(),
+            JsonDeserializeError::Data(e) => io::Error::new(io::ErrorKind::InvalidData, e),
+        }
+    }
+}
+
+/// A string that is either borrowed from the input, borrowed from the parser buffer or owned.
+enum Str<'de, 's> {
+    Borrowed(&'de str),
+    Copied(&'s str),
+    Owned(String),
+}
+
+impl<'de> Str<'de, '_> {
+    fn as_str(&self) -> &str {
+        match self {
+            Self::Borrowed(s) => s,
+            Self::Copied(s) => s,
+            Self::Owned(s) => s,
+        }
+    }
+
+    fn into_cow(self) -> Cow<'de, str> {
+        match self {
+            Self::Borrowed(s) => Cow::Borrowed(s),
+            Self::Copied(s) => Cow::Owned(s.into()),
+            Self::Owned(s) => Cow::Owned(s),
+        }
+    }
+
+    fn visit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JsonDeserializeError> {
+        match self {
+            Self::Borrowed(s) => visitor.visit_borrowed_str(s),
+            Self::Copied(s) => visitor.visit_str(s),
+            Self::Owned(s) => visitor.visit_string(s),
+        }
+    }
+}
+
+/// A [`JsonEvent`] where strings might be borrowed from the input for the `'de` lifetime.
+enum Token<'de, 's> {
+    String(Str<'de, 's>),
+    Number(Str<'de, 's>),
+    Boolean(bool),
+    Null,
+    StartArray,
+    EndArray,
+    StartObject,
+    EndObject,
+    ObjectKey(Str<'de, 's>),
+    Eof,
+    StringChunk(Str<'de, 's>),
+    ObjectKeyChunk(Str<'de, 's>),
+}
+
+impl<'de> Token<'de, '_> {
+    fn borrowed(event: JsonEvent<'de>) -> Self {
+        fn str(s: Cow<'_, str>) -> Str<'_, 'static> {
+            match s {
+                Cow::Borrowed(s) => Str::Borrowed(s),
+                Cow::Owned(s) => Str::Owned(s),
+            }
+        }
+        match event {
+            JsonEvent::String(s) => Token::String(str(s)),
+            JsonEvent::Number(n) => Token::Number(str(n)),
+            JsonEvent::Boolean(b) => Token::Boolean(b),
+            JsonEvent::Null => Token::Null,
+            JsonEvent::StartArray => Token::StartArray,
+            JsonEvent::EndArray => Token::EndArray,
+            JsonEvent::StartObject => Token::StartObject,
+            JsonEvent::EndObject => Token::EndObject,
+            JsonEvent::ObjectKey(k) => Token::ObjectKey(str(k)),
+            JsonEvent::Eof => Token::Eof,
+            JsonEvent::StringChunk(s) => Token::StringChunk(str(s)),
+            JsonEvent::ObjectKeyChunk(k) => Token::ObjectKeyChunk(str(k)),
+        }
+    }
+
+    fn into_event(self) -> JsonEvent<'de> {
+        match self {
+            Token::String(s) => JsonEvent::String(s.into_cow()),
+            Token::Number(n) => JsonEvent::Number(n.into_cow()),
+            Token::Boolean(b) => JsonEvent::Boolean(b),
+            Token::Null => JsonEvent::Null,
+            Token::StartArray => JsonEvent::StartArray,
+            Token::EndArray => JsonEvent::EndArray,
+            Token::StartObject => JsonEvent::StartObject,
+            Token::EndObject => JsonEvent::EndObject,
+            Token::ObjectKey(k) => JsonEvent::ObjectKey(k.into_cow()),
+            Token::Eof => JsonEvent::Eof,
+            Token::StringChunk(s) => JsonEvent::StringChunk(s.into_cow()),
+            Token::ObjectKeyChunk(k) => JsonEvent::ObjectKeyChunk(k.into_cow()),
+        }
+    }
+
+    fn unexpected(&self) -> JsonDeserializeError {
+        JsonDeserializeError::Data(
+            match self {
+                Token::EndArray => "Unexpected end of array, a value was expected",
+                Token::EndObject => "Unexpected end of object, a value was expected",
+                Token::ObjectKey(_) => "Unexpected object key, a value was expected",
+                Token::Eof => "Unexpected end of file, a value was expected",
+                _ => "Unexpected value",
+            }
+            .into(),
+        )
+    }
+}
+
+trait EventRead<'de> {
+    fn parse_next(&mut self) -> Result<Token<'de, '_>, JsonDeserializeError>;
+}
+
+struct SliceRead<'p, 'de> {
+    parser: &'p mut SliceJsonParser<'de>,
+}
+
+impl<'de> EventRead<'de> for SliceRead<'_, 'de> {
+    fn parse_next(&mut self) -> Result<Token<'de, '_>, JsonDeserializeError> {
+        Ok(Token::borrowed(self.parser.parse_next()?))
+    }
+}
+
+struct SourceRead<'p, S> {
+    source: &'p mut S,
+}
+
+impl<'de, S: JsonEventSource> EventRead<'de> for SourceRead<'_, S>
+where
+    S::Error: Into<JsonParseError>,
+{
+    fn parse_next(&mut self) -> Result<Token<'de, '_>, JsonDeserializeError> {
+        fn str(s: Cow<'_, str>) -> Str<'static, '_> {
+            match s {
+                Cow::Borrowed(s) => Str::Copied(s),
+                Cow::Owned(s) => Str::Owned(s),
+            }
+        }
+        Ok(match self.source.parse_next().map_err(|e| e.into())? {
+            JsonEvent::String(s) => Token::String(str(s)),
+            JsonEvent::Number(n) => Token::Number(str(n)),
+            JsonEvent::Boolean(b) => Token::Boolean(b),
+            JsonEvent::Null => Token::Null,
+            JsonEvent::StartArray => Token::StartArray,
+            JsonEvent::EndArray => Token::EndArray,
+            JsonEvent::StartObject => Token::StartObject,
+            JsonEvent::EndObject => Token::EndObject,
+            JsonEvent::ObjectKey(k) => Token::ObjectKey(str(k)),
+            JsonEvent::Eof => Token::Eof,
+            JsonEvent::StringChunk(s) => Token::StringChunk(str(s)),
+            JsonEvent::ObjectKeyChunk(k) => Token::ObjectKeyChunk(str(k)),
+        })
+    }
+}
+
+struct Deserializer<'de, R> {
+    read: R,
+    peeked: Option<JsonEvent<'de>>,
+    remaining_depth: u8,
+}
+
+impl<'de, R: EventRead<'de>> Deserializer<'de, R> {
+    fn new(read: R) -> Self {
+        Self {
+            read,
+            peeked: None,
+            remaining_depth: MAX_RECURSION_DEPTH,
+        }
+    }
+
+    /// Returns the next token, string and object key chunks being merged.
+    fn next_token(&mut self) -> Result<Token<'de, '_>, JsonDeserializeError> {
+        if let Some(event) = self.peeked.take() {
+            return Ok(Token::borrowed(event));
+        }
+        #[allow(unsafe_code)]
+        let token = unsafe {
+            let read: *mut R = &mut self.read;
+            &mut *read
+        } // SAFETY: Borrow checker workaround https://github.com/rust-lang/rust/issues/70255
+        .parse_next()?;
+        let (mut content, is_key) = match token {
+            Token::StringChunk(s) => (String::from(s.as_str()), false),
+            Token::ObjectKeyChunk(k) => (String::from(k.as_str()), true),
+            token => return Ok(token),
+        };
+        loop {
+            match (self.read.parse_next()?, is_key) {
+                (Token::StringChunk(s), false) | (Token::ObjectKeyChunk(s), true) => {
+                    content.push_str(s.as_str())
+                }
+                (Token::String(s), false) => {
+                    content.push_str(s.as_str());
+                    return Ok(Token::String(Str::Owned(content)));
+                }
+                (Token::ObjectKey(k), true) => {
+                    content.push_str(k.as_str());
+                    return Ok(Token::ObjectKey(Str::Owned(content)));
+                }
+                _ => {
+                    return Err(JsonDeserializeError::Data(
+                        "String chunks must be followed by the end of the string".into(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Puts back a token to be returned by the next call to [`next_token`](Self::next_token).
+    fn unread(&mut self, event: JsonEvent<'de>) {
+        debug_assert!(self.peeked.is_none());
+        self.peeked = Some(event);
+    }
+
+    fn enter_nested(&mut self) -> Result<(), JsonDeserializeError> {
+        self.remaining_depth = self.remaining_depth.checked_sub(1).ok_or_else(|| {
+            JsonDeserializeError::Data(format!(
+                "The maximal nesting depth of {MAX_RECURSION_DEPTH} has been reached"
+            ))
+        })?;
+        Ok(())
+    }
+
+    fn leave_nested(&mut self) {
+        self.remaining_depth += 1;
+    }
+
+    fn visit_array<V: Visitor<'de>>(
+        &mut self,
+        visitor: V,
+    ) -> Result<V::Value, JsonDeserializeError> {
+        self.enter_nested()?;
+        let mut access = ArrayAccess {
+            de: self,
+            ended: false,
+        };
+        let value = visitor.visit_seq(&mut access)?;
+        let ended = access.ended;
+        self.leave_nested();
+        if !ended {
+            match self.next_token()? {
+                Token::EndArray => (),
+                _ => {
+                    return Err(JsonDeserializeError::Data(
+                        "The array contains more elements than expected".into(),
+                    ))
+                }
+            }
+        }
+        Ok(value)
+    }
+
+    fn visit_object<V: Visitor<'de>>(
+        &mut self,
+        visitor: V,
+    ) -> Result<V::Value, JsonDeserializeError> {
+        self.enter_nested()?;
+        let mut access = ObjectAccess {
+            de: self,
+            ended: false,
+        };
+        let value = visitor.visit_map(&mut access)?;
+        let ended = access.ended;
+        self.leave_nested();
+        if !ended {
+            match self.next_token()? {
+                Token::EndObject => (),
+                _ => {
+                    return Err(JsonDeserializeError::Data(
+                        "The object contains more entries than expected".into(),
+                    ))
+                }
+            }
+        }
+        Ok(value)
+    }
+
+    fn skip_value(&mut self) -> Result<(), JsonDeserializeError> {
+        let mut depth = 0_usize;
+        loop {
+            match self.next_token()? {
+                Token::StartArray | Token::StartObject => depth += 1,
+                token @ (Token::EndArray | Token::EndObject) => {
+                    if depth == 0 {
+                        return Err(token.unexpected());
+                    }
+                    depth -= 1;
+                }
+                Token::ObjectKey(_) if depth > 0 => continue,
+                token @ (Token::ObjectKey(_) | Token::Eof) => return Err(token.unexpected()),
+                _ => (),
+            }
+            if depth == 0 {
+                return Ok(());
+            }
+        }
+    }
+}
+
+fn visit_number<'de, V: Visitor<'de>>(
+    number: &str,
+    visitor: V,
+) -> Result<V::Value, JsonDeserializeError> {
+    if !number.contains(['.', 'e', 'E']) {
+        if let Ok(value) = number.parse::<u64>() {
+            return visitor.visit_u64(value);
+        }
+        if let Ok(value) = number.parse::<i64>() {
+            return visitor.visit_i64(value);
+        }
+    }
+    visitor.visit_f64(
+        number
+            .parse()
+            .map_err(|e| JsonDeserializeError::Data(format!("Invalid number {number}: {e}")))?,
+    )
+}
+
+macro_rules! deserialize_number_from_str {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JsonDeserializeError> {
+                match self.next_token()? {
+                    Token::Number(n) => visitor.$visit(n.as_str().parse().map_err(|e| {
+                        JsonDeserializeError::Data(format!("Invalid number {}: {e}", n.as_str()))
+                    })?),
+                    token => {
+                        let event = token.into_event();
+                        self.unread(event);
+                        self.deserialize_any(visitor)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl<'de, R: EventRead<'de>> de::Deserializer<'de> for &mut Deserializer<'de, R> {
+    type Error = JsonDeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, JsonDeserializeError> {
+        match self.next_token()? {
+            Token::String(s) => s.visit(visitor),
+            Token::Number(n) => visit_number(n.as_str(), visitor),
+            Token::Boolean(b) => visitor.visit_bool(b),
+            Token::Null => visitor.visit_unit(),
+            Token::StartArray => self.visit_array(visitor),
+            Token::StartObject => self.visit_object(visitor),
+            token => Err(token.unexpected()),
+        }
+    }
+
+    deserialize_number_from_str! {
+        deserialize_i128 => visit_i128,
+        deserialize_u128 => visit_u128,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, JsonDeserializeError> {
+        match self.next_token()? {
+            Token::Null => visitor.visit_none(),
+            token => {
+                let event = token.into_event();
+                self.unread(event);
+                visitor.visit_some(self)
+            }
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, JsonDeserializeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, JsonDeserializeError> {
+        match self.next_token()? {
+            Token::String(s) => match s {
+                Str::Borrowed(s) => visitor.visit_enum(BorrowedStrDeserializer::new(s)),
+                Str::Copied(s) => visitor.visit_enum(StrDeserializer::new(s)),
+                Str::Owned(s) => visitor.visit_enum(StringDeserializer::new(s)),
+            },
+            Token::StartObject => {
+                self.enter_nested()?;
+                let value = visitor.visit_enum(VariantObjectAccess { de: self })?;
+                self.leave_nested();
+                match self.next_token()? {
+                    Token::EndObject => Ok(value),
+                    _ => Err(JsonDeserializeError::Data(
+                        "An enum must be encoded as an object with a single key".into(),
+                    )),
+                }
+            }
+            token => {
+                if matches!(
+                    token,
+                    Token::EndArray | Token::EndObject | Token::ObjectKey(_) | Token::Eof
+                ) {
+                    return Err(token.unexpected());
+                }
+                Err(JsonDeserializeError::Data(
+                    "An enum must be encoded as a string or an object with a single key".into(),
+                ))
+            }
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, JsonDeserializeError> {
+        self.skip_value()?;
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier
+    }
+}
+
+struct ArrayAccess<'a, 'de, R> {
+    de: &'a mut Deserializer<'de, R>,
+    ended: bool,
+}
+
+impl<'de, R: EventRead<'de>> SeqAccess<'de> for &mut ArrayAccess<'_, 'de, R> {
+    type Error = JsonDeserializeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, JsonDeserializeError> {
+        if self.ended {
+            return Ok(None);
+        }
+        match self.de.next_token()? {
+            Token::EndArray => {
+                self.ended = true;
+                Ok(None)
+            }
+            token => {
+                let event = token.into_event();
+                self.de.unread(event);
+                seed.deserialize(&mut *self.de).map(Some)
+            }
+        }
+    }
+}
+
+struct ObjectAccess<'a, 'de, R> {
+    de: &'a mut Deserializer<'de, R>,
+    ended: bool,
+}
+
+impl<'de, R: EventRead<'de>> MapAccess<'de> for &mut ObjectAccess<'_, 'de, R> {
+    type Error = JsonDeserializeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, JsonDeserializeError> {
+        if self.ended {
+            return Ok(None);
+        }
+        match self.de.next_token()? {
+            Token::EndObject => {
+                self.ended = true;
+                Ok(None)
+            }
+            Token::ObjectKey(key) => seed.deserialize(KeyDeserializer { key }).map(Some),
+            token => Err(token.unexpected()),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, JsonDeserializeError> {
+        seed.deserialize(&mut *self.de)
+    }
+}
+
+struct VariantObjectAccess<'a, 'de, R> {
+    de: &'a mut Deserializer<'de, R>,
+}
+
+impl<'de, R: EventRead<'de>> EnumAccess<'de> for VariantObjectAccess<'_, 'de, R> {
+    type Error = JsonDeserializeError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self), JsonDeserializeError> {
+        let variant = match self.de.next_token()? {
+            Token::ObjectKey(key) => seed.deserialize(KeyDeserializer { key })?,
+            _ => {
+                return Err(JsonDeserializeError::Data(
+                    "An enum must be encoded as an object with a single key".into(),
+                ))
+            }
+        };
+        Ok((variant, self))
+    }
+}
+
+impl<'de, R: EventRead<'de>> VariantAccess<'de> for VariantObjectAccess<'_, 'de, R> {
+    type Error = JsonDeserializeError;
+
+    fn unit_variant(self) -> Result<(), JsonDeserializeError> {
+        Deserialize::deserialize(&mut *self.de)
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, JsonDeserializeError> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, JsonDeserializeError> {
+        de::Deserializer::deserialize_seq(&mut *self.de, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, JsonDeserializeError> {
+        de::Deserializer::deserialize_map(&mut *self.de, visitor)
+    }
+}
+
+/// Deserializes object keys that are always strings in JSON.
+///
+/// Numbers are parsed from the string to allow maps with integer keys.
+struct KeyDeserializer<'de, 's> {
+    key: Str<'de, 's>,
+}
+
+macro_rules! deserialize_key_number {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JsonDeserializeError> {
+                let key = self.key.as_str();
+                visitor.$visit(key.parse().map_err(|e| {
+                    JsonDeserializeError::Data(format!("Invalid number {key} in object key: {e}"))
+                })?)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for KeyDeserializer<'de, '_> {
+    type Error = JsonDeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, JsonDeserializeError> {
+        self.key.visit(visitor)
+    }
+
+    deserialize_key_number! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, JsonDeserializeError> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, JsonDeserializeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, JsonDeserializeError> {
+        match self.key {
+            Str::Borrowed(s) => visitor.visit_enum(BorrowedStrDeserializer::new(s)),
+            Str::Copied(s) => visitor.visit_enum(StrDeserializer::new(s)),
+            Str::Owned(s) => visitor.visit_enum(StringDeserializer::new(s)),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool char str string bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
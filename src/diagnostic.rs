@@ -0,0 +1,206 @@
+use crate::{JsonSyntaxError, JsonValue, TextPosition};
+use std::fmt::Write;
+
+/// Spans covering more lines than this are rendered with their first and last lines only
+const MAX_RENDERED_LINES: usize = 3;
+
+/// Renders [`JsonSyntaxError`]s with the source lines they point to.
+///
+/// The source must be the UTF-8 bytes given to the parser.
+/// Invalid UTF-8 sequences are displayed as U+FFFD and tabulations are expanded to spaces.
+///
+/// ```
+/// use json_event_parser::{JsonDiagnosticRenderer, SliceJsonParser};
+///
+/// let source = b"{\n\t\"foo\": tru\n}";
+/// let mut parser = SliceJsonParser::new(source);
+/// parser.parse_next()?; // {
+/// parser.parse_next()?; // "foo"
+/// let error = parser.parse_next().unwrap_err();
+/// let renderer = JsonDiagnosticRenderer::new(source).with_file_name("config.json");
+/// assert_eq!(
+///     renderer.render(&error),
+///     "error: true expected
+///  --> config.json:2:12
+///   |
+/// 2 |     \"foo\": tru
+///   |            ^^^
+/// "
+/// );
+/// # std::io::Result::Ok(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[must_use]
+pub struct JsonDiagnosticRenderer<'a> {
+    source: &'a [u8],
+    file_name: Option<&'a str>,
+    tab_width: usize,
+}
+
+impl<'a> JsonDiagnosticRenderer<'a> {
+    #[inline]
+    pub const fn new(source: &'a [u8]) -> Self {
+        Self {
+            source,
+            file_name: None,
+            tab_width: 4,
+        }
+    }
+
+    /// Sets the file name displayed before the error position.
+    #[inline]
+    pub fn with_file_name(mut self, file_name: &'a str) -> Self {
+        self.file_name = Some(file_name);
+        self
+    }
+
+    /// Sets the number of spaces used to display a tabulation. 4 by default.
+    #[inline]
+    pub fn with_tab_width(mut self, tab_width: usize) -> Self {
+        self.tab_width = tab_width;
+        self
+    }
+
+    /// Renders the error message followed by the source lines with the error location underlined.
+    ///
+    /// Locations covering more than 3 lines are rendered with their first and last lines only.
+    pub fn render(&self, error: &JsonSyntaxError) -> String {
+        let location = error.location();
+        let start = self.clamp_offset(location.start.offset);
+        let end = self.clamp_offset(location.end.offset).max(start);
+        // The last byte of the span if it is not empty
+        let last = if end > start { end - 1 } else { start };
+
+        let mut lines = Vec::new();
+        let mut line_start = self.line_start(start);
+        loop {
+            let line_end = self.line_end(line_start);
+            lines.push(line_start..line_end);
+            if last <= line_end || line_end >= self.source.len() {
+                break;
+            }
+            line_start = line_end + 1;
+        }
+        let first_line_number = location.start.line + 1;
+        let last_line_number = first_line_number + u64::try_from(lines.len()).unwrap() - 1;
+        let gutter_width = last_line_number.to_string().len();
+
+        let mut output = String::new();
+        writeln!(output, "error: {}", error.message()).unwrap();
+        let column = self.display_width(lines[0].start, start) + 1;
+        match self.file_name {
+            Some(file_name) => writeln!(
+                output,
+                "{:gutter_width$}--> {file_name}:{first_line_number}:{column}",
+                ""
+            ),
+            None => writeln!(
+                output,
+                "{:gutter_width$}--> {first_line_number}:{column}",
+                ""
+            ),
+        }
+        .unwrap();
+        writeln!(output, "{:gutter_width$} |", "").unwrap();
+        for (i, line) in lines.iter().enumerate() {
+            if lines.len() > MAX_RENDERED_LINES && i > 0 && i < lines.len() - 1 {
+                if i == 1 {
+                    writeln!(output, "...").unwrap();
+                }
+                continue;
+            }
+            let line_number = first_line_number + u64::try_from(i).unwrap();
+            writeln!(
+                output,
+                "{line_number:>gutter_width$} | {}",
+                self.display_text(line.start, line.end)
+            )
+            .unwrap();
+            let underline_start = start.max(line.start);
+            let underline_end = end.min(line.end);
+            let padding = self.display_width(line.start, underline_start);
+            let underline_width = self.display_width(underline_start, underline_end).max(1);
+            writeln!(
+                output,
+                "{:gutter_width$} | {:padding$}{:^<underline_width$}",
+                "", "", ""
+            )
+            .unwrap();
+        }
+        output
+    }
+
+    /// Renders the error as a JSON object.
+    ///
+    /// The object contains the `message`, the `file` name if set and the `start` and `end` positions of the location
+    /// with the same `line`, `column` and `offset` keys as [`TextPosition`].
+    ///
+    /// ```
+    /// use json_event_parser::{JsonDiagnosticRenderer, SliceJsonParser};
+    ///
+    /// let error = SliceJsonParser::new(b"]").parse_next().unwrap_err();
+    /// assert_eq!(
+    ///     JsonDiagnosticRenderer::new(b"]").render_json(&error),
+    ///     r#"{"severity":"error","message":"Unexpected closing square bracket, no array to close","start":{"line":0,"column":0,"offset":0},"end":{"line":0,"column":1,"offset":1}}"#
+    /// );
+    /// ```
+    pub fn render_json(&self, error: &JsonSyntaxError) -> String {
+        let location = error.location();
+        let mut object = vec![
+            ("severity".into(), JsonValue::String("error".into())),
+            ("message".into(), JsonValue::String(error.message().into())),
+        ];
+        if let Some(file_name) = self.file_name {
+            object.push(("file".into(), JsonValue::String(file_name.into())));
+        }
+        object.push(("start".into(), position_to_json(location.start)));
+        object.push(("end".into(), position_to_json(location.end)));
+        JsonValue::Object(object).to_string()
+    }
+
+    fn clamp_offset(&self, offset: u64) -> usize {
+        usize::try_from(offset).map_or(self.source.len(), |o| o.min(self.source.len()))
+    }
+
+    fn line_start(&self, offset: usize) -> usize {
+        self.source[..offset]
+            .iter()
+            .rposition(|b| *b == b'\n')
+            .map_or(0, |i| i + 1)
+    }
+
+    /// The position of the `\n` ending the line or the end of the source
+    fn line_end(&self, line_start: usize) -> usize {
+        self.source[line_start..]
+            .iter()
+            .position(|b| *b == b'\n')
+            .map_or(self.source.len(), |i| line_start + i)
+    }
+
+    fn display_text(&self, start: usize, end: usize) -> String {
+        let text = String::from_utf8_lossy(&self.source[start..end]);
+        text.trim_end_matches('\r')
+            .replace('\t', &" ".repeat(self.tab_width))
+    }
+
+    fn display_width(&self, start: usize, end: usize) -> usize {
+        String::from_utf8_lossy(&self.source[start..end])
+            .chars()
+            .map(|c| if c == '\t' { self.tab_width } else { 1 })
+            .sum()
+    }
+}
+
+fn position_to_json(position: TextPosition) -> JsonValue {
+    JsonValue::Object(vec![
+        ("line".into(), JsonValue::Number(position.line.to_string())),
+        (
+            "column".into(),
+            JsonValue::Number(position.column.to_string()),
+        ),
+        (
+            "offset".into(),
+            JsonValue::Number(position.offset.to_string()),
+        ),
+    ])
+}
@@ -0,0 +1,258 @@
+use std::collections::VecDeque;
+
+/// Byte written in place of invalid UTF-16 or UTF-32 sequences.
+///
+/// It is never valid in UTF-8 so the lexer reports it as an invalid sequence.
+const INVALID_BYTE: u8 = 0xFF;
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum InputEncoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Utf32Le,
+    Utf32Be,
+}
+
+/// Transcodes UTF-16 and UTF-32 inputs to UTF-8 before lexing.
+///
+/// The encoding is detected from the byte order mark if present
+/// or from the pattern of null bytes in the first two characters ([RFC 4627 section 3](https://www.rfc-editor.org/rfc/rfc4627#section-3)).
+/// UTF-8 inputs are forwarded as they are.
+///
+/// The decoder keeps enough information to map offsets in the UTF-8 output back to offsets in the source.
+pub(crate) struct InputDecoder {
+    encoding: Option<InputEncoding>,
+    /// The source bytes not decoded yet followed by the space to read new ones
+    buffer: Vec<u8>,
+    pending_len: usize,
+    offsets: OffsetMap,
+}
+
+/// Mapping between offsets in the UTF-8 output and offsets in the source.
+struct OffsetMap {
+    source_offset: u64,
+    output_offset: u64,
+    /// Start of each run of characters with the same source and output lengths
+    segments: VecDeque<Segment>,
+}
+
+#[derive(Clone, Copy)]
+struct Segment {
+    output_start: u64,
+    source_start: u64,
+    output_char_len: u8,
+    source_char_len: u8,
+}
+
+impl InputDecoder {
+    pub(crate) const fn new() -> Self {
+        Self {
+            encoding: None,
+            buffer: Vec::new(),
+            pending_len: 0,
+            offsets: OffsetMap {
+                source_offset: 0,
+                output_offset: 0,
+                segments: VecDeque::new(),
+            },
+        }
+    }
+
+    /// Goes back to the beginning of a new input, keeping the allocated buffers.
+    pub(crate) fn reset(&mut self) {
+        self.encoding = None;
+        self.pending_len = 0;
+        self.offsets.source_offset = 0;
+        self.offsets.output_offset = 0;
+        self.offsets.segments.clear();
+    }
+
+    /// Returns the buffer to read new source bytes into.
+    ///
+    /// It is sized such that their decoding fits in an output of `output_len` bytes.
+    /// It is empty if `output_len` is too small.
+    pub(crate) fn source_buffer(&mut self, output_len: usize) -> &mut [u8] {
+        // A UTF-16 code unit is at most decoded into 3 bytes and a trailing truncated one into 1 byte
+        let len = (output_len.saturating_sub(1) / 3 * 2).saturating_sub(self.pending_len);
+        let end = self.pending_len + len;
+        if self.buffer.len() < end {
+            self.buffer.resize(end, 0);
+        }
+        &mut self.buffer[self.pending_len..end]
+    }
+
+    /// Decodes the `read` bytes added to the [source buffer](Self::source_buffer) into `output`.
+    ///
+    /// `read == 0` means the end of the input: everything left is then decoded.
+    /// Returns the number of bytes written into `output`.
+    pub(crate) fn decode(&mut self, read: usize, output: &mut [u8]) -> usize {
+        let is_ending = read == 0;
+        let input_len = self.pending_len + read;
+        let encoding = if let Some(encoding) = self.encoding {
+            encoding
+        } else {
+            let Some(encoding) = detect_encoding(&self.buffer[..input_len], is_ending) else {
+                self.pending_len = input_len;
+                return 0;
+            };
+            self.encoding = Some(encoding);
+            encoding
+        };
+        let input = &self.buffer[..input_len];
+        let mut input_position = 0;
+        let mut output_position = 0;
+        if encoding == InputEncoding::Utf8 {
+            output[..input_len].copy_from_slice(input);
+            input_position = input_len;
+            output_position = input_len;
+            self.offsets.source_offset += u64::try_from(input_len).unwrap();
+            self.offsets.output_offset += u64::try_from(input_len).unwrap();
+        } else {
+            while input_position < input_len {
+                let remaining = &input[input_position..];
+                let (c, source_char_len) = match encoding {
+                    InputEncoding::Utf8 => unreachable!(),
+                    InputEncoding::Utf16Le | InputEncoding::Utf16Be => {
+                        let read_unit = |bytes: &[u8]| {
+                            if encoding == InputEncoding::Utf16Le {
+                                u16::from_le_bytes([bytes[0], bytes[1]])
+                            } else {
+                                u16::from_be_bytes([bytes[0], bytes[1]])
+                            }
+                        };
+                        if remaining.len() < 2 {
+                            if !is_ending {
+                                break;
+                            }
+                            (None, remaining.len())
+                        } else {
+                            let unit = read_unit(remaining);
+                            if (0xD800..=0xDBFF).contains(&unit) {
+                                if remaining.len() < 4 && !is_ending {
+                                    break;
+                                }
+                                match remaining.get(2..4).map(read_unit) {
+                                    Some(low @ 0xDC00..=0xDFFF) => (
+                                        char::from_u32(
+                                            0x10000
+                                                + ((u32::from(unit) - 0xD800) << 10)
+                                                + (u32::from(low) - 0xDC00),
+                                        ),
+                                        4,
+                                    ),
+                                    _ => (None, 2),
+                                }
+                            } else {
+                                (char::from_u32(unit.into()), 2)
+                            }
+                        }
+                    }
+                    InputEncoding::Utf32Le | InputEncoding::Utf32Be => {
+                        if remaining.len() < 4 {
+                            if !is_ending {
+                                break;
+                            }
+                            (None, remaining.len())
+                        } else {
+                            let bytes = [remaining[0], remaining[1], remaining[2], remaining[3]];
+                            (
+                                char::from_u32(if encoding == InputEncoding::Utf32Le {
+                                    u32::from_le_bytes(bytes)
+                                } else {
+                                    u32::from_be_bytes(bytes)
+                                }),
+                                4,
+                            )
+                        }
+                    }
+                };
+                let output_char_len = if let Some(c) = c {
+                    c.encode_utf8(&mut output[output_position..]).len()
+                } else {
+                    output[output_position] = INVALID_BYTE;
+                    1
+                };
+                self.offsets.add_char(output_char_len, source_char_len);
+                input_position += source_char_len;
+                output_position += output_char_len;
+            }
+        }
+        self.buffer.copy_within(input_position..input_len, 0);
+        self.pending_len = input_len - input_position;
+        output_position
+    }
+
+    /// Maps an offset in the UTF-8 output to the matching offset in the source.
+    pub(crate) fn source_offset(&self, output_offset: u64) -> u64 {
+        self.offsets.source_offset(output_offset)
+    }
+
+    /// Drops the mapping information of the output before `output_offset`.
+    ///
+    /// Offsets before it can't be mapped precisely anymore.
+    pub(crate) fn forget_before(&mut self, output_offset: u64) {
+        self.offsets.forget_before(output_offset);
+    }
+}
+
+impl OffsetMap {
+    fn add_char(&mut self, output_char_len: usize, source_char_len: usize) {
+        let output_char_len = u8::try_from(output_char_len).unwrap();
+        let source_char_len = u8::try_from(source_char_len).unwrap();
+        if self.segments.back().map_or(true, |segment| {
+            segment.output_char_len != output_char_len || segment.source_char_len != source_char_len
+        }) {
+            self.segments.push_back(Segment {
+                output_start: self.output_offset,
+                source_start: self.source_offset,
+                output_char_len,
+                source_char_len,
+            });
+        }
+        self.output_offset += u64::from(output_char_len);
+        self.source_offset += u64::from(source_char_len);
+    }
+
+    fn source_offset(&self, output_offset: u64) -> u64 {
+        let index = self
+            .segments
+            .partition_point(|segment| segment.output_start <= output_offset);
+        let Some(segment) = self.segments.get(index.saturating_sub(1)) else {
+            return output_offset;
+        };
+        let chars =
+            output_offset.saturating_sub(segment.output_start) / u64::from(segment.output_char_len);
+        segment.source_start + chars * u64::from(segment.source_char_len)
+    }
+
+    fn forget_before(&mut self, output_offset: u64) {
+        while self
+            .segments
+            .get(1)
+            .is_some_and(|segment| segment.output_start <= output_offset)
+        {
+            self.segments.pop_front();
+        }
+    }
+}
+
+/// Detects the encoding from the first bytes of the input.
+///
+/// Returns `None` if more bytes are needed.
+fn detect_encoding(start: &[u8], is_ending: bool) -> Option<InputEncoding> {
+    if start.len() < 4 && !is_ending {
+        return None;
+    }
+    Some(match start {
+        [0, 0, 0xFE, 0xFF, ..] => InputEncoding::Utf32Be,
+        [0xFF, 0xFE, 0, 0, ..] => InputEncoding::Utf32Le,
+        [0xFE, 0xFF, ..] => InputEncoding::Utf16Be,
+        [0xFF, 0xFE, ..] => InputEncoding::Utf16Le,
+        [0, 0, 0, _, ..] => InputEncoding::Utf32Be,
+        [_, 0, 0, 0, ..] => InputEncoding::Utf32Le,
+        [0, _, ..] => InputEncoding::Utf16Be,
+        [_, 0, ..] => InputEncoding::Utf16Le,
+        _ => InputEncoding::Utf8,
+    })
+}
@@ -11,22 +11,57 @@
     unused_qualifications
 )]
 
+mod base64;
+mod buf_read;
+#[cfg(feature = "serde")]
+mod de;
+mod diagnostic;
+mod encoding;
+mod path;
 mod read;
+#[cfg(feature = "serde")]
+mod ser;
+mod sink;
+mod snapshot;
+mod source;
+mod split;
+mod value;
 mod write;
 
+pub use crate::base64::{decode_base64, Base64Alphabet, Base64DecodeError, Base64Decoder};
+pub use crate::buf_read::BufReaderJsonParser;
 #[cfg(feature = "async-tokio")]
-pub use crate::read::TokioAsyncReaderJsonParser;
+pub use crate::buf_read::TokioAsyncBufReaderJsonParser;
+#[cfg(feature = "serde")]
+pub use crate::de::JsonDeserializeError;
+pub use crate::diagnostic::JsonDiagnosticRenderer;
+pub use crate::path::{JsonPath, JsonPathSelection, JsonPathSyntaxError};
 pub use crate::read::{
-    JsonParseError, JsonSyntaxError, LowLevelJsonParser, LowLevelJsonParserResult,
-    ReaderJsonParser, SliceJsonParser, TextPosition,
+    JsonLimit, JsonLimits, JsonParseError, JsonSyntaxError, LoneSurrogate, LowLevelJsonParser,
+    LowLevelJsonParserResult, ReaderJsonParser, ReaderJsonParserIter, SliceJsonParser,
+    TextPosition,
 };
 #[cfg(feature = "async-tokio")]
+pub use crate::read::{TokioAsyncReaderJsonParser, TokioAsyncReaderJsonParserStream};
+#[cfg(feature = "async-tokio")]
+pub use crate::sink::TokioAsyncJsonEventSink;
+pub use crate::sink::{JsonEventSink, LowLevelJsonSerializerSink};
+pub use crate::source::{pipe_events, JsonEventSource};
+#[cfg(feature = "async-tokio")]
+pub use crate::source::{tokio_async_pipe_events, TokioAsyncJsonEventSource};
+pub use crate::split::{JsonArrayElement, ReaderJsonArraySplitter};
+pub use crate::value::{JsonValue, JsonValueEvents};
+#[cfg(feature = "async-tokio")]
 pub use crate::write::TokioAsyncWriterJsonSerializer;
 pub use crate::write::{LowLevelJsonSerializer, WriterJsonSerializer};
 use std::borrow::Cow;
+use std::{fmt, str};
 
 /// Possible events during JSON parsing.
+///
+/// New kinds of events might be added in minor releases.
 #[derive(Eq, PartialEq, Debug, Clone, Hash)]
+#[non_exhaustive]
 pub enum JsonEvent<'a> {
     String(Cow<'a, str>),
     Number(Cow<'a, str>),
@@ -38,6 +73,196 @@ pub enum JsonEvent<'a> {
     EndObject,
     ObjectKey(Cow<'a, str>),
     Eof,
+    /// A part of a string value, followed by other chunks and by a [`JsonEvent::String`] with the end of the string.
+    ///
+    /// Only returned by parsers configured with a string chunk size.
+    StringChunk(Cow<'a, str>),
+    /// A part of an object key, followed by other chunks and by a [`JsonEvent::ObjectKey`] with the end of the key.
+    ///
+    /// Only returned by parsers configured with a string chunk size.
+    ObjectKeyChunk(Cow<'a, str>),
+}
+
+impl JsonEvent<'_> {
+    /// Converts the event into an event that does not borrow any data.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, SliceJsonParser};
+    ///
+    /// let mut reader = SliceJsonParser::new(b"\"foo\"");
+    /// let event: JsonEvent<'static> = reader.parse_next()?.into_owned();
+    /// assert_eq!(event, JsonEvent::String("foo".into()));
+    /// # std::io::Result::Ok(())
+    /// ```
+    #[inline]
+    pub fn into_owned(self) -> JsonEvent<'static> {
+        match self {
+            Self::String(s) => JsonEvent::String(s.into_owned().into()),
+            Self::Number(n) => JsonEvent::Number(n.into_owned().into()),
+            Self::Boolean(b) => JsonEvent::Boolean(b),
+            Self::Null => JsonEvent::Null,
+            Self::StartArray => JsonEvent::StartArray,
+            Self::EndArray => JsonEvent::EndArray,
+            Self::StartObject => JsonEvent::StartObject,
+            Self::EndObject => JsonEvent::EndObject,
+            Self::ObjectKey(k) => JsonEvent::ObjectKey(k.into_owned().into()),
+            Self::Eof => JsonEvent::Eof,
+            Self::StringChunk(s) => JsonEvent::StringChunk(s.into_owned().into()),
+            Self::ObjectKeyChunk(k) => JsonEvent::ObjectKeyChunk(k.into_owned().into()),
+        }
+    }
+
+    /// Returns a copy of the event borrowing the data of this event.
+    ///
+    /// ```
+    /// use json_event_parser::JsonEvent;
+    ///
+    /// let event = JsonEvent::String(String::from("foo").into());
+    /// assert_eq!(event.as_borrowed(), JsonEvent::String("foo".into()));
+    /// ```
+    #[inline]
+    pub fn as_borrowed(&self) -> JsonEvent<'_> {
+        match self {
+            Self::String(s) => JsonEvent::String(Cow::Borrowed(s)),
+            Self::Number(n) => JsonEvent::Number(Cow::Borrowed(n)),
+            Self::Boolean(b) => JsonEvent::Boolean(*b),
+            Self::Null => JsonEvent::Null,
+            Self::StartArray => JsonEvent::StartArray,
+            Self::EndArray => JsonEvent::EndArray,
+            Self::StartObject => JsonEvent::StartObject,
+            Self::EndObject => JsonEvent::EndObject,
+            Self::ObjectKey(k) => JsonEvent::ObjectKey(Cow::Borrowed(k)),
+            Self::Eof => JsonEvent::Eof,
+            Self::StringChunk(s) => JsonEvent::StringChunk(Cow::Borrowed(s)),
+            Self::ObjectKeyChunk(k) => JsonEvent::ObjectKeyChunk(Cow::Borrowed(k)),
+        }
+    }
+}
+
+/// Writes the JSON token of the event.
+///
+/// Strings and object keys are escaped and [`JsonEvent::Eof`] is written as an empty string.
+/// String and object key chunks are written escaped without quotes.
+///
+/// ```
+/// use json_event_parser::JsonEvent;
+///
+/// assert_eq!(JsonEvent::String("f\"o".into()).to_string(), "\"f\\\"o\"");
+/// assert_eq!(JsonEvent::Number("-1.2".into()).to_string(), "-1.2");
+/// assert_eq!(JsonEvent::StartObject.to_string(), "{");
+/// ```
+impl fmt::Display for JsonEvent<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::String(s) | Self::ObjectKey(s) => {
+                let mut buffer = Vec::with_capacity(s.len() + 2);
+                write::write_escaped_json_string(s, &mut buffer).map_err(|_| fmt::Error)?;
+                f.write_str(str::from_utf8(&buffer).map_err(|_| fmt::Error)?)
+            }
+            Self::StringChunk(s) | Self::ObjectKeyChunk(s) => {
+                let mut buffer = Vec::with_capacity(s.len());
+                write::write_escaped_json_string_content(s, &mut buffer).map_err(|_| fmt::Error)?;
+                f.write_str(str::from_utf8(&buffer).map_err(|_| fmt::Error)?)
+            }
+            Self::Number(n) => f.write_str(n),
+            Self::Boolean(true) => f.write_str("true"),
+            Self::Boolean(false) => f.write_str("false"),
+            Self::Null => f.write_str("null"),
+            Self::StartArray => f.write_str("["),
+            Self::EndArray => f.write_str("]"),
+            Self::StartObject => f.write_str("{"),
+            Self::EndObject => f.write_str("}"),
+            Self::Eof => Ok(()),
+        }
+    }
+}
+
+impl From<bool> for JsonEvent<'static> {
+    #[inline]
+    fn from(value: bool) -> Self {
+        Self::Boolean(value)
+    }
+}
+
+impl From<()> for JsonEvent<'static> {
+    #[inline]
+    fn from((): ()) -> Self {
+        Self::Null
+    }
+}
+
+impl<'a> From<&'a str> for JsonEvent<'a> {
+    #[inline]
+    fn from(value: &'a str) -> Self {
+        Self::String(Cow::Borrowed(value))
+    }
+}
+
+impl From<String> for JsonEvent<'static> {
+    #[inline]
+    fn from(value: String) -> Self {
+        Self::String(Cow::Owned(value))
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for JsonEvent<'a> {
+    #[inline]
+    fn from(value: Cow<'a, str>) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<char> for JsonEvent<'static> {
+    #[inline]
+    fn from(value: char) -> Self {
+        Self::String(Cow::Owned(value.into()))
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for JsonEvent<'static> {
+                #[inline]
+                fn from(value: $t) -> Self {
+                    Self::Number(Cow::Owned(value.to_string()))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Non finite values (NaN and infinities) are converted to [`JsonEvent::Null`] because JSON is not able to represent them.
+impl From<f32> for JsonEvent<'static> {
+    #[inline]
+    fn from(value: f32) -> Self {
+        if value.is_finite() {
+            Self::Number(Cow::Owned(value.to_string()))
+        } else {
+            Self::Null
+        }
+    }
+}
+
+/// Non finite values (NaN and infinities) are converted to [`JsonEvent::Null`] because JSON is not able to represent them.
+///
+/// ```
+/// use json_event_parser::JsonEvent;
+///
+/// assert_eq!(JsonEvent::from(1.5), JsonEvent::Number("1.5".into()));
+/// assert_eq!(JsonEvent::from(f64::NAN), JsonEvent::Null);
+/// ```
+impl From<f64> for JsonEvent<'static> {
+    #[inline]
+    fn from(value: f64) -> Self {
+        if value.is_finite() {
+            Self::Number(Cow::Owned(value.to_string()))
+        } else {
+            Self::Null
+        }
+    }
 }
 
 #[cfg(feature = "async-tokio")]
@@ -0,0 +1,644 @@
+use crate::{JsonEvent, JsonEventSource, JsonParseError};
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::ops::Range;
+use std::str::FromStr;
+
+/// A [JSONPath](https://www.rfc-editor.org/rfc/rfc9535) query that can be evaluated on a stream of events.
+///
+/// Only the subset of JSONPath that can be evaluated without buffering is supported:
+/// - child segments with names (`.foo`, `['foo']`), wildcards (`.*`, `[*]`), non-negative indices (`[1]`)
+///   and slices with non-negative bounds and a non-negative step (`[1:5:2]`),
+/// - descendant segments using the same selectors (`..foo`, `..*`, `..[0]`),
+/// - unions of selectors (`['foo', 1]`).
+///
+/// Filters, functions and negative indices are not supported.
+/// Unlike RFC 9535, matches are always returned in document order, even with unions like `['b', 'a']`.
+///
+/// ```
+/// use json_event_parser::{JsonEvent, JsonPath, SliceJsonParser};
+///
+/// let path = JsonPath::parse("$.results[*].id")?;
+/// let parser = SliceJsonParser::new(br#"{"results": [{"id": 1, "a": [2]}, {"id": "b"}], "id": 3}"#);
+/// let ids = path.select(parser).collect::<Result<Vec<_>, _>>()?;
+/// assert_eq!(
+///     ids,
+///     [
+///         vec![JsonEvent::Number("1".into())],
+///         vec![JsonEvent::String("b".into())]
+///     ]
+/// );
+/// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct JsonPath {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Segment {
+    Child(Vec<Selector>),
+    Descendant(Vec<Selector>),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Selector {
+    Name(String),
+    Wildcard,
+    Index(usize),
+    Slice {
+        start: usize,
+        end: Option<usize>,
+        step: usize,
+    },
+}
+
+/// Position of a value inside of its parent.
+enum Location<'a> {
+    Key(&'a str),
+    Index(usize),
+}
+
+impl Selector {
+    fn matches(&self, location: &Location<'_>) -> bool {
+        match (self, location) {
+            (Self::Wildcard, _) => true,
+            (Self::Name(name), Location::Key(key)) => name == key,
+            (Self::Index(i), Location::Index(index)) => i == index,
+            (Self::Slice { start, end, step }, Location::Index(index)) => {
+                *step > 0
+                    && index >= start
+                    && end.map_or(true, |end| *index < end)
+                    && (index - start) % step == 0
+            }
+            _ => false,
+        }
+    }
+}
+
+impl JsonPath {
+    /// Parses a JSONPath query.
+    ///
+    /// ```
+    /// use json_event_parser::JsonPath;
+    ///
+    /// assert!(JsonPath::parse("$..book[0, 2:4]['title']").is_ok());
+    /// assert!(JsonPath::parse("$.book[-1]").is_err());
+    /// ```
+    pub fn parse(path: &str) -> Result<Self, JsonPathSyntaxError> {
+        PathParser {
+            input: path,
+            position: 0,
+        }
+        .parse()
+    }
+
+    /// Evaluates the query on the next value of `source`.
+    ///
+    /// The returned iterator yields the events of each matched value, in document order.
+    /// Values that do not match are skipped without being buffered,
+    /// so the memory used is bounded by the size of the largest match.
+    ///
+    /// If matches are nested inside of each other (e.g. with `$..a`), all of them are returned, outer ones first.
+    pub fn select<S: JsonEventSource>(&self, source: S) -> JsonPathSelection<S>
+    where
+        S::Error: Into<JsonParseError>,
+    {
+        JsonPathSelection {
+            source,
+            path: self.clone(),
+            frames: Vec::new(),
+            skip_depth: 0,
+            active_matches: Vec::new(),
+            ready_matches: VecDeque::new(),
+            is_ending: false,
+            string_in_chunks: None,
+        }
+    }
+
+    /// Computes the states of a value from the states of its parent.
+    ///
+    /// A state `i` means that the value is reached after matching the `i` first segments.
+    fn step(&self, parent_states: &[usize], location: &Location<'_>) -> Vec<usize> {
+        let mut states = Vec::new();
+        for &i in parent_states {
+            let Some(segment) = self.segments.get(i) else {
+                continue;
+            };
+            let selectors = match segment {
+                Segment::Child(selectors) => selectors,
+                Segment::Descendant(selectors) => {
+                    if !states.contains(&i) {
+                        states.push(i);
+                    }
+                    selectors
+                }
+            };
+            if selectors.iter().any(|s| s.matches(location)) && !states.contains(&(i + 1)) {
+                states.push(i + 1);
+            }
+        }
+        states
+    }
+}
+
+impl FromStr for JsonPath {
+    type Err = JsonPathSyntaxError;
+
+    #[inline]
+    fn from_str(path: &str) -> Result<Self, JsonPathSyntaxError> {
+        Self::parse(path)
+    }
+}
+
+/// Iterator on the values selected by a [`JsonPath`].
+///
+/// Can be built using [`JsonPath::select`].
+#[must_use]
+pub struct JsonPathSelection<S: JsonEventSource> {
+    source: S,
+    path: JsonPath,
+    frames: Vec<Frame>,
+    skip_depth: usize,
+    active_matches: Vec<ActiveMatch>,
+    ready_matches: VecDeque<Vec<JsonEvent<'static>>>,
+    is_ending: bool,
+    /// If a string returned in chunks is being read, if it is a match
+    string_in_chunks: Option<bool>,
+}
+
+/// An array or an object being read.
+struct Frame {
+    /// The query states of the children, empty if no child might match
+    states: Vec<usize>,
+    next_index: usize,
+    key: Option<String>,
+    is_object: bool,
+    is_match: bool,
+}
+
+/// A matched value whose events are being collected.
+struct ActiveMatch {
+    events: Vec<JsonEvent<'static>>,
+    /// The matches nested inside of this one that are already complete
+    nested_matches: Vec<Vec<JsonEvent<'static>>>,
+}
+
+impl<S: JsonEventSource> JsonPathSelection<S>
+where
+    S::Error: Into<JsonParseError>,
+{
+    fn parse_next_match(&mut self) -> Result<Option<Vec<JsonEvent<'static>>>, JsonParseError> {
+        loop {
+            if let Some(events) = self.ready_matches.pop_front() {
+                return Ok(Some(events));
+            }
+            if self.is_ending {
+                return Ok(None);
+            }
+            let event = self.source.parse_next().map_err(Into::into)?;
+            if self.skip_depth > 0 {
+                match event {
+                    JsonEvent::StartArray | JsonEvent::StartObject => self.skip_depth += 1,
+                    JsonEvent::EndArray | JsonEvent::EndObject => self.skip_depth -= 1,
+                    JsonEvent::Eof => return Err(invalid_data("Unexpected end of file")),
+                    _ => (),
+                }
+                continue;
+            }
+            match event {
+                JsonEvent::Eof => {
+                    if !self.frames.is_empty() {
+                        return Err(invalid_data("Unexpected end of file"));
+                    }
+                    self.is_ending = true;
+                }
+                JsonEvent::StringChunk(_) | JsonEvent::String(_)
+                    if self.string_in_chunks.is_some() =>
+                {
+                    let is_end = matches!(event, JsonEvent::String(_));
+                    record(&mut self.active_matches, event);
+                    if is_end {
+                        if self.string_in_chunks.take() == Some(true) {
+                            self.complete_match();
+                        }
+                        if self.frames.is_empty() {
+                            self.is_ending = true;
+                        }
+                    }
+                }
+                JsonEvent::ObjectKey(ref key) | JsonEvent::ObjectKeyChunk(ref key) => {
+                    let Some(frame) = self.frames.last_mut().filter(|f| f.is_object) else {
+                        return Err(invalid_data("Unexpected object key"));
+                    };
+                    if !frame.states.is_empty() {
+                        // Chunks are concatenated
+                        frame.key.get_or_insert_with(String::new).push_str(key);
+                    }
+                    record(&mut self.active_matches, event);
+                }
+                JsonEvent::EndArray | JsonEvent::EndObject => {
+                    let is_object = matches!(event, JsonEvent::EndObject);
+                    let Some(frame) = self.frames.pop().filter(|f| f.is_object == is_object) else {
+                        return Err(invalid_data("Unexpected container end"));
+                    };
+                    record(&mut self.active_matches, event);
+                    if frame.is_match {
+                        self.complete_match();
+                    }
+                    if self.frames.is_empty() {
+                        self.is_ending = true;
+                    }
+                }
+                event => {
+                    let states = match self.frames.last_mut() {
+                        Some(parent) if parent.states.is_empty() => Vec::new(),
+                        Some(parent) => {
+                            let states = if parent.is_object {
+                                let Some(key) = parent.key.take() else {
+                                    return Err(invalid_data("Object values must follow a key"));
+                                };
+                                self.path.step(&parent.states, &Location::Key(&key))
+                            } else {
+                                self.path
+                                    .step(&parent.states, &Location::Index(parent.next_index))
+                            };
+                            parent.next_index += 1;
+                            states
+                        }
+                        None => vec![0],
+                    };
+                    let is_match = states.contains(&self.path.segments.len());
+                    let is_container =
+                        matches!(event, JsonEvent::StartArray | JsonEvent::StartObject);
+                    let is_chunk = matches!(event, JsonEvent::StringChunk(_));
+                    if is_chunk {
+                        self.string_in_chunks = Some(is_match);
+                    }
+                    if !is_match && self.active_matches.is_empty() {
+                        if is_container && states.is_empty() {
+                            self.skip_depth = 1;
+                            continue;
+                        }
+                        if !is_container {
+                            if self.frames.is_empty() && !is_chunk {
+                                self.is_ending = true;
+                            }
+                            continue;
+                        }
+                    }
+                    if is_match {
+                        self.active_matches.push(ActiveMatch {
+                            events: Vec::new(),
+                            nested_matches: Vec::new(),
+                        });
+                    }
+                    let is_object = matches!(event, JsonEvent::StartObject);
+                    record(&mut self.active_matches, event);
+                    if is_container {
+                        self.frames.push(Frame {
+                            states: states
+                                .into_iter()
+                                .filter(|i| *i < self.path.segments.len())
+                                .collect(),
+                            next_index: 0,
+                            key: None,
+                            is_object,
+                            is_match,
+                        });
+                    } else if !is_chunk {
+                        if is_match {
+                            self.complete_match();
+                        }
+                        if self.frames.is_empty() {
+                            self.is_ending = true;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Completes the innermost match being collected.
+    fn complete_match(&mut self) {
+        let Some(ActiveMatch {
+            events,
+            nested_matches,
+        }) = self.active_matches.pop()
+        else {
+            return;
+        };
+        let target = if let Some(parent) = self.active_matches.last_mut() {
+            &mut parent.nested_matches
+        } else {
+            self.ready_matches
+                .extend([events].into_iter().chain(nested_matches));
+            return;
+        };
+        target.push(events);
+        target.extend(nested_matches);
+    }
+}
+
+impl<S: JsonEventSource> Iterator for JsonPathSelection<S>
+where
+    S::Error: Into<JsonParseError>,
+{
+    type Item = Result<Vec<JsonEvent<'static>>, JsonParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.parse_next_match() {
+            Ok(events) => events.map(Ok),
+            Err(e) => {
+                self.is_ending = true;
+                self.ready_matches.clear();
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// Adds the event to all the matches being collected.
+fn record(active_matches: &mut [ActiveMatch], event: JsonEvent<'_>) {
+    if let Some((last, others)) = active_matches.split_last_mut() {
+        let event = event.into_owned();
+        for active_match in others {
+            active_match.events.push(event.clone());
+        }
+        last.events.push(event);
+    }
+}
+
+fn invalid_data(message: &str) -> JsonParseError {
+    io::Error::new(io::ErrorKind::InvalidData, message).into()
+}
+
+/// An error in the syntax of a [`JsonPath`] query.
+#[derive(Debug)]
+pub struct JsonPathSyntaxError {
+    location: Range<usize>,
+    message: String,
+}
+
+impl JsonPathSyntaxError {
+    /// The byte range of the error inside of the query.
+    #[inline]
+    pub fn location(&self) -> Range<usize> {
+        self.location.clone()
+    }
+
+    /// The error message.
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for JsonPathSyntaxError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "JSONPath syntax error between bytes {} and {}: {}",
+            self.location.start, self.location.end, self.message
+        )
+    }
+}
+
+impl Error for JsonPathSyntaxError {}
+
+struct PathParser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl PathParser<'_> {
+    fn parse(mut self) -> Result<JsonPath, JsonPathSyntaxError> {
+        if !self.eat('$') {
+            return Err(self.error(0..1, "JSONPath queries must start with '$'"));
+        }
+        let mut segments = Vec::new();
+        loop {
+            self.skip_blank();
+            if self.position == self.input.len() {
+                return Ok(JsonPath { segments });
+            }
+            if self.input[self.position..].starts_with("..") {
+                self.position += 2;
+                segments.push(Segment::Descendant(if self.eat('[') {
+                    self.parse_bracketed_selectors()?
+                } else {
+                    vec![self.parse_shorthand_selector()?]
+                }));
+            } else if self.eat('.') {
+                segments.push(Segment::Child(vec![self.parse_shorthand_selector()?]));
+            } else if self.eat('[') {
+                segments.push(Segment::Child(self.parse_bracketed_selectors()?));
+            } else {
+                return Err(self.error_at_current("Expecting '.', '..' or '['"));
+            }
+        }
+    }
+
+    fn parse_shorthand_selector(&mut self) -> Result<Selector, JsonPathSyntaxError> {
+        if self.eat('*') {
+            return Ok(Selector::Wildcard);
+        }
+        let start = self.position;
+        for (i, c) in self.input[start..].char_indices() {
+            let is_valid = c == '_'
+                || c.is_ascii_alphabetic()
+                || (c.is_ascii_digit() && i > 0)
+                || !c.is_ascii();
+            if !is_valid {
+                break;
+            }
+            self.position = start + i + c.len_utf8();
+        }
+        if self.position == start {
+            return Err(self.error_at_current("Expecting a member name or '*'"));
+        }
+        Ok(Selector::Name(self.input[start..self.position].into()))
+    }
+
+    fn parse_bracketed_selectors(&mut self) -> Result<Vec<Selector>, JsonPathSyntaxError> {
+        let mut selectors = Vec::new();
+        loop {
+            self.skip_blank();
+            selectors.push(self.parse_bracketed_selector()?);
+            self.skip_blank();
+            if self.eat(']') {
+                return Ok(selectors);
+            }
+            if !self.eat(',') {
+                return Err(self.error_at_current("Expecting ',' or ']'"));
+            }
+        }
+    }
+
+    fn parse_bracketed_selector(&mut self) -> Result<Selector, JsonPathSyntaxError> {
+        match self.peek() {
+            Some('*') => {
+                self.position += 1;
+                Ok(Selector::Wildcard)
+            }
+            Some(quote @ ('\'' | '"')) => {
+                self.position += 1;
+                self.parse_string(quote).map(Selector::Name)
+            }
+            Some('?') => Err(self.error_at_current("Filter selectors are not supported")),
+            _ => {
+                let start = self.parse_integer()?;
+                self.skip_blank();
+                if !self.eat(':') {
+                    return start
+                        .map(Selector::Index)
+                        .ok_or_else(|| self.error_at_current("Expecting a selector"));
+                }
+                self.skip_blank();
+                let end = self.parse_integer()?;
+                self.skip_blank();
+                let step = if self.eat(':') {
+                    self.skip_blank();
+                    self.parse_integer()?
+                } else {
+                    None
+                };
+                Ok(Selector::Slice {
+                    start: start.unwrap_or(0),
+                    end,
+                    step: step.unwrap_or(1),
+                })
+            }
+        }
+    }
+
+    /// Parses a non-negative integer if there is one
+    fn parse_integer(&mut self) -> Result<Option<usize>, JsonPathSyntaxError> {
+        let start = self.position;
+        if self.eat('-') {
+            return Err(self.error(
+                start..self.position,
+                "Negative indices are not supported on streams",
+            ));
+        }
+        let digits = self.input[start..]
+            .bytes()
+            .take_while(u8::is_ascii_digit)
+            .count();
+        if digits == 0 {
+            return Ok(None);
+        }
+        self.position += digits;
+        let value = &self.input[start..self.position];
+        if digits > 1 && value.starts_with('0') {
+            return Err(self.error(start..self.position, "Integers must not have leading zeros"));
+        }
+        value
+            .parse()
+            .map(Some)
+            .map_err(|_| self.error(start..self.position, "The integer is too big"))
+    }
+
+    fn parse_string(&mut self, quote: char) -> Result<String, JsonPathSyntaxError> {
+        let mut value = String::new();
+        loop {
+            let start = self.position;
+            let Some(c) = self.peek() else {
+                return Err(self.error_at_current("Unterminated string"));
+            };
+            self.position += c.len_utf8();
+            match c {
+                c if c == quote => return Ok(value),
+                '\\' => {
+                    let Some(escape) = self.peek() else {
+                        return Err(self.error_at_current("Unterminated string"));
+                    };
+                    self.position += escape.len_utf8();
+                    value.push(match escape {
+                        'b' => '\u{8}',
+                        'f' => '\u{C}',
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        '/' | '\\' | '\'' | '"' => escape,
+                        'u' => self.parse_unicode_escape(start)?,
+                        _ => {
+                            return Err(self.error(start..self.position, "Invalid escape sequence"))
+                        }
+                    });
+                }
+                '\0'..='\u{1F}' => {
+                    return Err(
+                        self.error(start..self.position, "Control characters must be escaped")
+                    )
+                }
+                c => value.push(c),
+            }
+        }
+    }
+
+    fn parse_unicode_escape(&mut self, start: usize) -> Result<char, JsonPathSyntaxError> {
+        let high = self.parse_hex_code_unit(start)?;
+        let code_point = if (0xD800..=0xDBFF).contains(&high) {
+            if !self.input[self.position..].starts_with("\\u") {
+                return Err(self.error(start..self.position, "Unpaired surrogate"));
+            }
+            self.position += 2;
+            let low = self.parse_hex_code_unit(start)?;
+            if !(0xDC00..=0xDFFF).contains(&low) {
+                return Err(self.error(start..self.position, "Unpaired surrogate"));
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+        char::from_u32(code_point)
+            .ok_or_else(|| self.error(start..self.position, "Unpaired surrogate"))
+    }
+
+    fn parse_hex_code_unit(&mut self, start: usize) -> Result<u32, JsonPathSyntaxError> {
+        let hex = self
+            .input
+            .get(self.position..self.position + 4)
+            .filter(|h| h.bytes().all(|b| b.is_ascii_hexdigit()))
+            .ok_or_else(|| self.error(start..self.position, "Invalid \\u escape sequence"))?;
+        self.position += 4;
+        Ok(u32::from_str_radix(hex, 16).unwrap())
+    }
+
+    fn skip_blank(&mut self) {
+        let blank = self.input[self.position..]
+            .bytes()
+            .take_while(|b| matches!(b, b' ' | b'\t' | b'\n' | b'\r'))
+            .count();
+        self.position += blank;
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.input[self.position..].starts_with(c) {
+            self.position += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn error_at_current(&self, message: &str) -> JsonPathSyntaxError {
+        let end = self.position + self.peek().map_or(0, char::len_utf8);
+        self.error(self.position..end, message)
+    }
+
+    fn error(&self, location: Range<usize>, message: &str) -> JsonPathSyntaxError {
+        JsonPathSyntaxError {
+            location,
+            message: message.into(),
+        }
+    }
+}
@@ -1,16 +1,30 @@
+use crate::encoding::InputDecoder;
+use crate::snapshot::{invalid_snapshot, SnapshotReader, SnapshotWriter};
 use crate::JsonEvent;
+#[cfg(feature = "async-tokio")]
+use futures_core::Stream;
 use std::borrow::Cow;
 use std::cmp::{max, min};
+use std::collections::HashSet;
+use std::convert::Infallible;
 use std::error::Error;
 use std::io::{self, Read};
 use std::ops::Range;
-use std::{fmt, str};
 #[cfg(feature = "async-tokio")]
-use tokio::io::{AsyncRead, AsyncReadExt};
+use std::pin::Pin;
+#[cfg(feature = "async-tokio")]
+use std::task::{ready, Context, Poll};
+use std::{fmt, mem, str};
+#[cfg(feature = "async-tokio")]
+use tokio::io::{AsyncRead, AsyncReadExt, ReadBuf};
 
 const MAX_STATE_STACK_SIZE: usize = 65_536;
+pub(crate) const MAX_TRACKED_OBJECT_KEYS_SIZE: usize = 1 << 24;
+const MAX_RECOVERED_ERRORS: usize = 100;
+/// The biggest integer allowed by I-JSON: 2^53 - 1
+const MAX_I_JSON_INTEGER: u64 = (1 << 53) - 1;
 const MIN_BUFFER_SIZE: usize = 4096;
-const MAX_BUFFER_SIZE: usize = 4096 * 4096;
+pub(crate) const MAX_BUFFER_SIZE: usize = 4096 * 4096;
 
 /// Parses a JSON file from a [`Read`] implementation.
 ///
@@ -34,6 +48,9 @@ pub struct ReaderJsonParser<R: Read> {
     is_ending: bool,
     read: R,
     parser: LowLevelJsonParser,
+    input_decoder: Option<InputDecoder>,
+    /// The bytes consumed since the capture has been started with [`start_raw_capture`](Self::start_raw_capture)
+    raw_capture: Option<Vec<u8>>,
 }
 
 impl<R: Read> ReaderJsonParser<R> {
@@ -47,6 +64,8 @@ impl<R: Read> ReaderJsonParser<R> {
             is_ending: false,
             read,
             parser: LowLevelJsonParser::new(),
+            input_decoder: None,
+            raw_capture: None,
         }
     }
 
@@ -57,10 +76,273 @@ impl<R: Read> ReaderJsonParser<R> {
         self
     }
 
+    /// Returns strings and object keys longer than `size` bytes in chunks.
+    ///
+    /// It allows to read strings bigger than the [maximal buffer size](Self::with_max_buffer_size) that must be greater than `size`.
+    /// See [`LowLevelJsonParser::with_string_chunk_size`] for details.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, ReaderJsonParser};
+    ///
+    /// let json = format!("[\"{}\"]", "a".repeat(10_000));
+    /// let mut reader = ReaderJsonParser::new(json.as_bytes())
+    ///     .with_max_buffer_size(4096)
+    ///     .with_string_chunk_size(1024);
+    /// assert_eq!(reader.parse_next()?, JsonEvent::StartArray);
+    /// let mut string = String::new();
+    /// loop {
+    ///     match reader.parse_next()? {
+    ///         JsonEvent::StringChunk(chunk) => string.push_str(&chunk),
+    ///         JsonEvent::String(end) => {
+    ///             string.push_str(&end);
+    ///             break;
+    ///         }
+    ///         _ => unreachable!(),
+    ///     }
+    /// }
+    /// assert_eq!(string.len(), 10_000);
+    /// assert_eq!(reader.parse_next()?, JsonEvent::EndArray);
+    /// # std::io::Result::Ok(())
+    /// ```
+    #[inline]
+    pub fn with_string_chunk_size(mut self, size: usize) -> Self {
+        self.parser = self.parser.with_string_chunk_size(size);
+        self
+    }
+
+    /// Maximal allowed number of nested object and array openings.
+    ///
+    /// See [`LowLevelJsonParser::with_max_stack_size`] for details.
+    #[inline]
+    pub fn with_max_stack_size(mut self, size: usize) -> Self {
+        self.parser = self.parser.with_max_stack_size(size);
+        self
+    }
+
+    /// Returns an error if an object contains twice the same key.
+    ///
+    /// See [`LowLevelJsonParser::with_duplicate_object_key_detection`] for details.
+    #[inline]
+    pub fn with_duplicate_object_key_detection(mut self) -> Self {
+        self.parser = self.parser.with_duplicate_object_key_detection();
+        self
+    }
+
+    /// Maximal total size in bytes of the object keys kept in memory for duplicate key detection.
+    ///
+    /// See [`LowLevelJsonParser::with_max_tracked_object_keys_size`] for details.
+    #[inline]
+    pub fn with_max_tracked_object_keys_size(mut self, size: usize) -> Self {
+        self.parser = self.parser.with_max_tracked_object_keys_size(size);
+        self
+    }
+
+    /// Validates that the input follows the [I-JSON](https://www.rfc-editor.org/rfc/rfc7493) profile.
+    ///
+    /// See [`LowLevelJsonParser::with_i_json_validation`] for details.
+    #[inline]
+    pub fn with_i_json_validation(mut self) -> Self {
+        self.parser = self.parser.with_i_json_validation();
+        self
+    }
+
+    /// Sets limits on the size of the parsed input to protect against malicious documents.
+    ///
+    /// See [`LowLevelJsonParser::with_limits`] for details.
+    #[inline]
+    pub fn with_limits(mut self, limits: JsonLimits) -> Self {
+        self.parser = self.parser.with_limits(limits);
+        self
+    }
+
+    /// Allows escaped lone UTF-16 surrogates like `"\uD888"` in strings and object keys.
+    ///
+    /// See [`LowLevelJsonParser::with_lone_surrogate_preservation`] for details.
+    #[inline]
+    pub fn with_lone_surrogate_preservation(mut self) -> Self {
+        self.parser = self.parser.with_lone_surrogate_preservation();
+        self
+    }
+
+    /// The lone surrogates replaced by U+FFFD in the string or object key returned by the last call to `parse_next`.
+    ///
+    /// See [`LowLevelJsonParser::lone_surrogates`] for details.
+    #[inline]
+    pub fn lone_surrogates(&self) -> &[LoneSurrogate] {
+        self.parser.lone_surrogates()
+    }
+
+    /// Replaces invalid UTF-8 sequences and lone surrogate escapes in strings and object keys by U+FFFD instead of returning an error.
+    ///
+    /// See [`LowLevelJsonParser::with_lossy_decoding`] for details.
+    #[inline]
+    pub fn with_lossy_decoding(mut self) -> Self {
+        self.parser = self.parser.with_lossy_decoding();
+        self
+    }
+
+    /// The invalid sequences replaced by U+FFFD in the string or object key returned by the last call to `parse_next`.
+    ///
+    /// See [`LowLevelJsonParser::decoding_warnings`] for details.
+    #[inline]
+    pub fn decoding_warnings(&self) -> &[JsonSyntaxError] {
+        self.parser.decoding_warnings()
+    }
+
+    /// Collects the recoverable syntax errors instead of returning them and repairs the returned events.
+    ///
+    /// See [`LowLevelJsonParser::with_error_recovery`] for details.
+    #[inline]
+    pub fn with_error_recovery(mut self) -> Self {
+        self.parser = self.parser.with_error_recovery();
+        self
+    }
+
+    /// Maximal number of errors collected in [error recovery mode](Self::with_error_recovery).
+    ///
+    /// See [`LowLevelJsonParser::with_max_recovered_errors`] for details.
+    #[inline]
+    pub fn with_max_recovered_errors(mut self, count: usize) -> Self {
+        self.parser = self.parser.with_max_recovered_errors(count);
+        self
+    }
+
+    /// The errors collected since the beginning of the parsing in [error recovery mode](Self::with_error_recovery).
+    ///
+    /// See [`LowLevelJsonParser::diagnostics`] for details.
+    #[inline]
+    pub fn diagnostics(&self) -> &[JsonSyntaxError] {
+        self.parser.diagnostics()
+    }
+
+    /// The position in the input after the bytes consumed so far.
+    ///
+    /// See [`LowLevelJsonParser::position`] for details.
+    #[inline]
+    pub fn position(&self) -> TextPosition {
+        self.parser.position()
+    }
+
+    /// Serializes the parsing state into a compact byte snapshot.
+    ///
+    /// The parsing can be resumed with a new parser reading the input from the [`position`](Self::position) offset
+    /// after a call to [`restore_snapshot`](Self::restore_snapshot).
+    /// The data read from the input but not parsed yet is not part of the snapshot.
+    /// See [`LowLevelJsonParser::snapshot`] for details.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, ReaderJsonParser};
+    /// use std::io::{Cursor, Seek, SeekFrom};
+    ///
+    /// let mut file = Cursor::new(b"[1, 2, 3]");
+    /// let mut reader = ReaderJsonParser::new(&mut file);
+    /// assert_eq!(reader.parse_next()?, JsonEvent::StartArray);
+    /// assert_eq!(reader.parse_next()?, JsonEvent::Number("1".into()));
+    /// let snapshot = reader.snapshot();
+    /// let offset = reader.position().offset;
+    ///
+    /// file.seek(SeekFrom::Start(offset))?;
+    /// let mut reader = ReaderJsonParser::new(file);
+    /// reader.restore_snapshot(&snapshot)?;
+    /// assert_eq!(reader.parse_next()?, JsonEvent::Number("2".into()));
+    /// assert_eq!(reader.parse_next()?, JsonEvent::Number("3".into()));
+    /// assert_eq!(reader.parse_next()?, JsonEvent::EndArray);
+    /// # std::io::Result::Ok(())
+    /// ```
+    #[inline]
+    pub fn snapshot(&self) -> Vec<u8> {
+        self.parser.snapshot()
+    }
+
+    /// Restores the parsing state from a snapshot built by [`snapshot`](Self::snapshot).
+    ///
+    /// The data already read from the input is discarded: the input must be at the [`position`](Self::position) offset of the snapshot.
+    /// Snapshots are not supported with [encoding detection](Self::with_encoding_detection).
+    /// See [`LowLevelJsonParser::restore_snapshot`] for details.
+    pub fn restore_snapshot(&mut self, snapshot: &[u8]) -> io::Result<()> {
+        if self.input_decoder.is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Snapshots are not supported with encoding detection",
+            ));
+        }
+        self.parser.restore_snapshot(snapshot)?;
+        self.input_buffer_start = 0;
+        self.input_buffer_end = 0;
+        self.is_ending = false;
+        Ok(())
+    }
+
+    /// Resets the parser to parse a new document from the same input.
+    ///
+    /// The data already read from the input is discarded.
+    /// The allocated buffers and the configuration are kept.
+    /// See [`LowLevelJsonParser::reset`] for details.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, ReaderJsonParser};
+    ///
+    /// let mut reader = ReaderJsonParser::new(b"[1]".as_slice());
+    /// assert_eq!(reader.parse_next()?, JsonEvent::StartArray);
+    /// let old_input = reader.reset_with_reader(b"{}".as_slice());
+    /// assert_eq!(old_input, b"");
+    /// assert_eq!(reader.parse_next()?, JsonEvent::StartObject);
+    /// assert_eq!(reader.parse_next()?, JsonEvent::EndObject);
+    /// assert_eq!(reader.parse_next()?, JsonEvent::Eof);
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn reset(&mut self) {
+        self.input_buffer_start = 0;
+        self.input_buffer_end = 0;
+        self.is_ending = false;
+        self.parser.reset();
+        if let Some(input_decoder) = &mut self.input_decoder {
+            input_decoder.reset();
+        }
+        self.raw_capture = None;
+    }
+
+    /// Resets the parser to parse a new document from `read` and returns the previous input.
+    ///
+    /// See [`reset`](Self::reset) for details.
+    pub fn reset_with_reader(&mut self, read: R) -> R {
+        self.reset();
+        mem::replace(&mut self.read, read)
+    }
+
+    /// Detects UTF-16 and UTF-32 inputs and transcodes them to UTF-8 before parsing.
+    ///
+    /// The encoding is detected from the byte order mark if present
+    /// or else from the pattern of null bytes in the first two characters, as described in [RFC 4627 section 3](https://www.rfc-editor.org/rfc/rfc4627#section-3).
+    /// Other inputs are parsed as UTF-8.
+    /// Error locations are given in bytes of the original input.
+    /// Invalid UTF-16 and UTF-32 sequences are reported like invalid UTF-8 sequences.
+    ///
+    /// The strings captured as raw bytes, for example by [`ReaderJsonArraySplitter::next_raw_element`](crate::ReaderJsonArraySplitter::next_raw_element),
+    /// and the [document size limit](JsonLimits::with_max_document_size) are in UTF-8.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, ReaderJsonParser};
+    ///
+    /// let file = b"\xFF\xFE[\x001\x00]\x00";
+    /// let mut reader = ReaderJsonParser::new(file.as_slice()).with_encoding_detection();
+    /// assert_eq!(reader.parse_next()?, JsonEvent::StartArray);
+    /// assert_eq!(reader.parse_next()?, JsonEvent::Number("1".into()));
+    /// assert_eq!(reader.parse_next()?, JsonEvent::EndArray);
+    /// assert_eq!(reader.parse_next()?, JsonEvent::Eof);
+    /// # std::io::Result::Ok(())
+    /// ```
+    #[inline]
+    pub fn with_encoding_detection(mut self) -> Self {
+        self.input_decoder = Some(InputDecoder::new());
+        self
+    }
+
     #[inline]
     pub fn parse_next(&mut self) -> Result<JsonEvent<'_>, JsonParseError> {
         loop {
             {
+                let reads_tokens = self.parser.reads_tokens();
                 let LowLevelJsonParserResult {
                     event,
                     consumed_bytes,
@@ -73,9 +355,15 @@ impl<R: Read> ReaderJsonParser<R> {
                     }, // SAFETY: Borrow checker workaround https://github.com/rust-lang/rust/issues/70255
                     self.is_ending,
                 );
+                if let Some(raw_capture) = &mut self.raw_capture {
+                    raw_capture.extend_from_slice(
+                        &self.input_buffer
+                            [self.input_buffer_start..self.input_buffer_start + consumed_bytes],
+                    );
+                }
                 self.input_buffer_start += consumed_bytes;
                 if let Some(event) = event {
-                    return Ok(event?);
+                    return Ok(self.map_to_source_positions(event, reads_tokens)?);
                 }
             }
             if self.input_buffer_start > 0 {
@@ -84,15 +372,8 @@ impl<R: Read> ReaderJsonParser<R> {
                 self.input_buffer_end -= self.input_buffer_start;
                 self.input_buffer_start = 0;
             }
-            if self.input_buffer.len() == self.max_buffer_size {
-                return Err(io::Error::new(
-                    io::ErrorKind::OutOfMemory,
-                    format!(
-                        "Reached the buffer maximal size of {}",
-                        self.max_buffer_size
-                    ),
-                )
-                .into());
+            if self.input_buffer_end >= self.max_buffer_size {
+                return Err(max_buffer_size_error(self.max_buffer_size).into());
             }
             let min_end = min(
                 self.input_buffer_end + MIN_BUFFER_SIZE,
@@ -105,18 +386,138 @@ impl<R: Read> ReaderJsonParser<R> {
                 // We keep extending to have as much space as available without reallocation
                 self.input_buffer.resize(self.input_buffer.capacity(), 0);
             }
-            let read = self
-                .read
-                .read(&mut self.input_buffer[self.input_buffer_end..])?;
-            self.input_buffer_end += read;
+            let read = if let Some(input_decoder) = &mut self.input_decoder {
+                let output = &mut self.input_buffer[self.input_buffer_end..];
+                let source_buffer = input_decoder.source_buffer(output.len());
+                if source_buffer.is_empty() {
+                    return Err(max_buffer_size_error(self.max_buffer_size).into());
+                }
+                let read = self.read.read(source_buffer)?;
+                self.input_buffer_end += input_decoder.decode(read, output);
+                read
+            } else {
+                let read = self
+                    .read
+                    .read(&mut self.input_buffer[self.input_buffer_end..])?;
+                self.input_buffer_end += read;
+                read
+            };
             self.is_ending = read == 0;
         }
     }
 
+    /// Maps the error and decoding warnings positions to the source input if it is transcoded
+    fn map_to_source_positions<'a>(
+        &mut self,
+        event: Result<JsonEvent<'a>, JsonSyntaxError>,
+        reads_tokens: bool,
+    ) -> Result<JsonEvent<'a>, JsonSyntaxError> {
+        let Some(input_decoder) = &mut self.input_decoder else {
+            return event;
+        };
+        map_to_source_positions(input_decoder, &mut self.parser, event, reads_tokens)
+    }
+
+    /// Returns the next event without consuming it.
+    ///
+    /// The event is kept and returned again by the next call to [`parse_next`](Self::parse_next).
+    /// Errors are not kept: they are returned by this method only.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, ReaderJsonParser};
+    ///
+    /// let mut reader = ReaderJsonParser::new(b"[\"foo\"]".as_slice());
+    /// assert_eq!(reader.peek_next()?, &JsonEvent::StartArray);
+    /// assert_eq!(reader.parse_next()?, JsonEvent::StartArray);
+    /// assert_eq!(reader.peek_next()?, &JsonEvent::String("foo".into()));
+    /// assert_eq!(reader.peek_next()?, &JsonEvent::String("foo".into()));
+    /// assert_eq!(reader.parse_next()?, JsonEvent::String("foo".into()));
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn peek_next(&mut self) -> Result<&JsonEvent<'static>, JsonParseError> {
+        if self.parser.buffered_event.is_none() {
+            let event = self.parse_next()?.into_owned();
+            self.parser.buffer_peeked_event(event);
+        }
+        Ok(self.parser.buffered_event.as_ref().unwrap())
+    }
+
     #[deprecated(note = "Use parse_next() instead")]
     pub fn read_next_event(&mut self) -> Result<JsonEvent<'_>, JsonParseError> {
         self.parse_next()
     }
+
+    /// Starts to record all the consumed bytes.
+    ///
+    /// Returns the byte offset in the input where the recording starts.
+    pub(crate) fn start_raw_capture(&mut self) -> u64 {
+        self.raw_capture = Some(Vec::new());
+        self.parser.lexer.file_offset
+    }
+
+    /// Stops the recording started by [`start_raw_capture`](Self::start_raw_capture) and returns the recorded bytes.
+    pub(crate) fn end_raw_capture(&mut self) -> Vec<u8> {
+        self.raw_capture.take().unwrap_or_default()
+    }
+
+    /// The byte offset in the input where the token of the last returned event starts.
+    pub(crate) fn last_token_start(&self) -> u64 {
+        self.parser.lexer.file_start_of_last_token
+    }
+
+    /// The maximal number of nested arrays and objects if set with [`LowLevelJsonParser::with_max_stack_size`].
+    #[inline]
+    pub(crate) fn max_stack_size(&self) -> Option<usize> {
+        self.parser.max_stack_size()
+    }
+}
+
+impl<R: Read> IntoIterator for ReaderJsonParser<R> {
+    type Item = Result<JsonEvent<'static>, JsonParseError>;
+    type IntoIter = ReaderJsonParserIter<R>;
+
+    #[inline]
+    fn into_iter(self) -> ReaderJsonParserIter<R> {
+        ReaderJsonParserIter { parser: self }
+    }
+}
+
+/// An iterator over the events of a [`ReaderJsonParser`].
+///
+/// Events are converted to owned events so that they do not borrow the parser internal buffer.
+/// The iterator ends when the end of the file is reached, the [`JsonEvent::Eof`] event is not returned.
+///
+/// ```
+/// use json_event_parser::{JsonEvent, ReaderJsonParser};
+///
+/// let events = ReaderJsonParser::new(b"{\"foo\": 1}".as_slice())
+///     .into_iter()
+///     .collect::<Result<Vec<_>, _>>()?;
+/// assert_eq!(
+///     events,
+///     [
+///         JsonEvent::StartObject,
+///         JsonEvent::ObjectKey("foo".into()),
+///         JsonEvent::Number("1".into()),
+///         JsonEvent::EndObject
+///     ]
+/// );
+/// # std::io::Result::Ok(())
+/// ```
+pub struct ReaderJsonParserIter<R: Read> {
+    parser: ReaderJsonParser<R>,
+}
+
+impl<R: Read> Iterator for ReaderJsonParserIter<R> {
+    type Item = Result<JsonEvent<'static>, JsonParseError>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.parser.parse_next() {
+            Ok(JsonEvent::Eof) => None,
+            result => Some(result.map(JsonEvent::into_owned)),
+        }
+    }
 }
 
 /// Parses a JSON file from an [`AsyncRead`] implementation.
@@ -147,6 +548,7 @@ pub struct TokioAsyncReaderJsonParser<R: AsyncRead + Unpin> {
     is_ending: bool,
     read: R,
     parser: LowLevelJsonParser,
+    input_decoder: Option<InputDecoder>,
 }
 
 #[cfg(feature = "async-tokio")]
@@ -161,6 +563,7 @@ impl<R: AsyncRead + Unpin> TokioAsyncReaderJsonParser<R> {
             is_ending: false,
             read,
             parser: LowLevelJsonParser::new(),
+            input_decoder: None,
         }
     }
 
@@ -171,10 +574,194 @@ impl<R: AsyncRead + Unpin> TokioAsyncReaderJsonParser<R> {
         self
     }
 
+    /// Returns strings and object keys longer than `size` bytes in chunks.
+    ///
+    /// It allows to read strings bigger than the [maximal buffer size](Self::with_max_buffer_size) that must be greater than `size`.
+    /// See [`LowLevelJsonParser::with_string_chunk_size`] for details.
+    #[inline]
+    pub fn with_string_chunk_size(mut self, size: usize) -> Self {
+        self.parser = self.parser.with_string_chunk_size(size);
+        self
+    }
+
+    /// Maximal allowed number of nested object and array openings.
+    ///
+    /// See [`LowLevelJsonParser::with_max_stack_size`] for details.
+    #[inline]
+    pub fn with_max_stack_size(mut self, size: usize) -> Self {
+        self.parser = self.parser.with_max_stack_size(size);
+        self
+    }
+
+    /// Returns an error if an object contains twice the same key.
+    ///
+    /// See [`LowLevelJsonParser::with_duplicate_object_key_detection`] for details.
+    #[inline]
+    pub fn with_duplicate_object_key_detection(mut self) -> Self {
+        self.parser = self.parser.with_duplicate_object_key_detection();
+        self
+    }
+
+    /// Maximal total size in bytes of the object keys kept in memory for duplicate key detection.
+    ///
+    /// See [`LowLevelJsonParser::with_max_tracked_object_keys_size`] for details.
+    #[inline]
+    pub fn with_max_tracked_object_keys_size(mut self, size: usize) -> Self {
+        self.parser = self.parser.with_max_tracked_object_keys_size(size);
+        self
+    }
+
+    /// Validates that the input follows the [I-JSON](https://www.rfc-editor.org/rfc/rfc7493) profile.
+    ///
+    /// See [`LowLevelJsonParser::with_i_json_validation`] for details.
+    #[inline]
+    pub fn with_i_json_validation(mut self) -> Self {
+        self.parser = self.parser.with_i_json_validation();
+        self
+    }
+
+    /// Sets limits on the size of the parsed input to protect against malicious documents.
+    ///
+    /// See [`LowLevelJsonParser::with_limits`] for details.
+    #[inline]
+    pub fn with_limits(mut self, limits: JsonLimits) -> Self {
+        self.parser = self.parser.with_limits(limits);
+        self
+    }
+
+    /// Allows escaped lone UTF-16 surrogates like `"\uD888"` in strings and object keys.
+    ///
+    /// See [`LowLevelJsonParser::with_lone_surrogate_preservation`] for details.
+    #[inline]
+    pub fn with_lone_surrogate_preservation(mut self) -> Self {
+        self.parser = self.parser.with_lone_surrogate_preservation();
+        self
+    }
+
+    /// The lone surrogates replaced by U+FFFD in the string or object key returned by the last call to `parse_next`.
+    ///
+    /// See [`LowLevelJsonParser::lone_surrogates`] for details.
+    #[inline]
+    pub fn lone_surrogates(&self) -> &[LoneSurrogate] {
+        self.parser.lone_surrogates()
+    }
+
+    /// Replaces invalid UTF-8 sequences and lone surrogate escapes in strings and object keys by U+FFFD instead of returning an error.
+    ///
+    /// See [`LowLevelJsonParser::with_lossy_decoding`] for details.
+    #[inline]
+    pub fn with_lossy_decoding(mut self) -> Self {
+        self.parser = self.parser.with_lossy_decoding();
+        self
+    }
+
+    /// The invalid sequences replaced by U+FFFD in the string or object key returned by the last call to `parse_next`.
+    ///
+    /// See [`LowLevelJsonParser::decoding_warnings`] for details.
+    #[inline]
+    pub fn decoding_warnings(&self) -> &[JsonSyntaxError] {
+        self.parser.decoding_warnings()
+    }
+
+    /// Collects the recoverable syntax errors instead of returning them and repairs the returned events.
+    ///
+    /// See [`LowLevelJsonParser::with_error_recovery`] for details.
+    #[inline]
+    pub fn with_error_recovery(mut self) -> Self {
+        self.parser = self.parser.with_error_recovery();
+        self
+    }
+
+    /// Maximal number of errors collected in [error recovery mode](Self::with_error_recovery).
+    ///
+    /// See [`LowLevelJsonParser::with_max_recovered_errors`] for details.
+    #[inline]
+    pub fn with_max_recovered_errors(mut self, count: usize) -> Self {
+        self.parser = self.parser.with_max_recovered_errors(count);
+        self
+    }
+
+    /// The errors collected since the beginning of the parsing in [error recovery mode](Self::with_error_recovery).
+    ///
+    /// See [`LowLevelJsonParser::diagnostics`] for details.
+    #[inline]
+    pub fn diagnostics(&self) -> &[JsonSyntaxError] {
+        self.parser.diagnostics()
+    }
+
+    /// The position in the input after the bytes consumed so far.
+    ///
+    /// See [`LowLevelJsonParser::position`] for details.
+    #[inline]
+    pub fn position(&self) -> TextPosition {
+        self.parser.position()
+    }
+
+    /// Serializes the parsing state into a compact byte snapshot.
+    ///
+    /// The parsing can be resumed with a new parser reading the input from the [`position`](Self::position) offset
+    /// after a call to [`restore_snapshot`](Self::restore_snapshot).
+    /// The data read from the input but not parsed yet is not part of the snapshot.
+    /// See [`LowLevelJsonParser::snapshot`] for details.
+    #[inline]
+    pub fn snapshot(&self) -> Vec<u8> {
+        self.parser.snapshot()
+    }
+
+    /// Restores the parsing state from a snapshot built by [`snapshot`](Self::snapshot).
+    ///
+    /// The data already read from the input is discarded: the input must be at the [`position`](Self::position) offset of the snapshot.
+    /// Snapshots are not supported with [encoding detection](Self::with_encoding_detection).
+    /// See [`LowLevelJsonParser::restore_snapshot`] for details.
+    pub fn restore_snapshot(&mut self, snapshot: &[u8]) -> io::Result<()> {
+        if self.input_decoder.is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Snapshots are not supported with encoding detection",
+            ));
+        }
+        self.parser.restore_snapshot(snapshot)?;
+        self.input_buffer_start = 0;
+        self.input_buffer_end = 0;
+        self.is_ending = false;
+        Ok(())
+    }
+
+    /// Resets the parser to parse a new document from the same input.
+    ///
+    /// See [`ReaderJsonParser::reset`] for details.
+    pub fn reset(&mut self) {
+        self.input_buffer_start = 0;
+        self.input_buffer_end = 0;
+        self.is_ending = false;
+        self.parser.reset();
+        if let Some(input_decoder) = &mut self.input_decoder {
+            input_decoder.reset();
+        }
+    }
+
+    /// Resets the parser to parse a new document from `read` and returns the previous input.
+    ///
+    /// See [`reset`](Self::reset) for details.
+    pub fn reset_with_reader(&mut self, read: R) -> R {
+        self.reset();
+        mem::replace(&mut self.read, read)
+    }
+
+    /// Detects UTF-16 and UTF-32 inputs and transcodes them to UTF-8 before parsing.
+    ///
+    /// See [`ReaderJsonParser::with_encoding_detection`] for details.
+    #[inline]
+    pub fn with_encoding_detection(mut self) -> Self {
+        self.input_decoder = Some(InputDecoder::new());
+        self
+    }
+
     #[inline]
     pub async fn parse_next(&mut self) -> Result<JsonEvent<'_>, JsonParseError> {
         loop {
             {
+                let reads_tokens = self.parser.reads_tokens();
                 let LowLevelJsonParserResult {
                     event,
                     consumed_bytes,
@@ -189,51 +776,193 @@ impl<R: AsyncRead + Unpin> TokioAsyncReaderJsonParser<R> {
                 );
                 self.input_buffer_start += consumed_bytes;
                 if let Some(event) = event {
-                    return Ok(event?);
+                    return Ok(self.map_to_source_positions(event, reads_tokens)?);
                 }
             }
-            if self.input_buffer_start > 0 {
-                self.input_buffer
-                    .copy_within(self.input_buffer_start..self.input_buffer_end, 0);
-                self.input_buffer_end -= self.input_buffer_start;
-                self.input_buffer_start = 0;
-            }
-            if self.input_buffer.len() == self.max_buffer_size {
-                return Err(io::Error::new(
-                    io::ErrorKind::OutOfMemory,
-                    format!(
-                        "Reached the buffer maximal size of {}",
-                        self.max_buffer_size
-                    ),
-                )
-                .into());
-            }
-            let min_end = min(
-                self.input_buffer_end + MIN_BUFFER_SIZE,
-                self.max_buffer_size,
-            );
-            if self.input_buffer.len() < min_end {
-                self.input_buffer.resize(min_end, 0);
-            }
-            if self.input_buffer.len() < self.input_buffer.capacity() {
-                // We keep extending to have as much space as available without reallocation
-                self.input_buffer.resize(self.input_buffer.capacity(), 0);
-            }
-            let read = self
-                .read
-                .read(&mut self.input_buffer[self.input_buffer_end..])
-                .await?;
-            self.input_buffer_end += read;
+            self.prepare_input_buffer_for_read()?;
+            let read = if let Some(input_decoder) = &mut self.input_decoder {
+                let output = &mut self.input_buffer[self.input_buffer_end..];
+                let source_buffer = input_decoder.source_buffer(output.len());
+                if source_buffer.is_empty() {
+                    return Err(max_buffer_size_error(self.max_buffer_size).into());
+                }
+                let read = self.read.read(source_buffer).await?;
+                self.input_buffer_end += input_decoder.decode(read, output);
+                read
+            } else {
+                let read = self
+                    .read
+                    .read(&mut self.input_buffer[self.input_buffer_end..])
+                    .await?;
+                self.input_buffer_end += read;
+                read
+            };
             self.is_ending = read == 0;
         }
     }
 
+    /// Converts the parser into a [`Stream`] of owned events.
+    ///
+    /// The stream ends when the end of the file is reached, the [`JsonEvent::Eof`] event is not returned.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, TokioAsyncReaderJsonParser};
+    /// use std::future::poll_fn;
+    /// use std::pin::pin;
+    /// use futures_core::Stream;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> ::std::io::Result<()> {
+    /// let mut stream = pin!(TokioAsyncReaderJsonParser::new(b"[1]".as_slice()).into_stream());
+    /// let mut events = Vec::new();
+    /// while let Some(event) = poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+    ///     events.push(event?);
+    /// }
+    /// assert_eq!(
+    ///     events,
+    ///     [
+    ///         JsonEvent::StartArray,
+    ///         JsonEvent::Number("1".into()),
+    ///         JsonEvent::EndArray
+    ///     ]
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_stream(self) -> TokioAsyncReaderJsonParserStream<R> {
+        TokioAsyncReaderJsonParserStream { parser: self }
+    }
+
+    fn poll_parse_next_owned(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<JsonEvent<'static>, JsonParseError>> {
+        loop {
+            let reads_tokens = self.parser.reads_tokens();
+            let LowLevelJsonParserResult {
+                event,
+                consumed_bytes,
+            } = self.parser.parse_next(
+                &self.input_buffer[self.input_buffer_start..self.input_buffer_end],
+                self.is_ending,
+            );
+            self.input_buffer_start += consumed_bytes;
+            if let Some(event) = event {
+                let event = event.map(JsonEvent::into_owned);
+                return Poll::Ready(Ok(self.map_to_source_positions(event, reads_tokens)?));
+            }
+            self.prepare_input_buffer_for_read()?;
+            let read = if let Some(input_decoder) = &mut self.input_decoder {
+                let output = &mut self.input_buffer[self.input_buffer_end..];
+                let source_buffer = input_decoder.source_buffer(output.len());
+                if source_buffer.is_empty() {
+                    return Poll::Ready(Err(max_buffer_size_error(self.max_buffer_size).into()));
+                }
+                let mut buffer = ReadBuf::new(source_buffer);
+                ready!(Pin::new(&mut self.read).poll_read(cx, &mut buffer))?;
+                let read = buffer.filled().len();
+                self.input_buffer_end += input_decoder.decode(read, output);
+                read
+            } else {
+                let mut buffer = ReadBuf::new(&mut self.input_buffer[self.input_buffer_end..]);
+                ready!(Pin::new(&mut self.read).poll_read(cx, &mut buffer))?;
+                let read = buffer.filled().len();
+                self.input_buffer_end += read;
+                read
+            };
+            self.is_ending = read == 0;
+        }
+    }
+
+    /// Maps the error and decoding warnings positions to the source input if it is transcoded
+    fn map_to_source_positions<'a>(
+        &mut self,
+        event: Result<JsonEvent<'a>, JsonSyntaxError>,
+        reads_tokens: bool,
+    ) -> Result<JsonEvent<'a>, JsonSyntaxError> {
+        let Some(input_decoder) = &mut self.input_decoder else {
+            return event;
+        };
+        map_to_source_positions(input_decoder, &mut self.parser, event, reads_tokens)
+    }
+
+    fn prepare_input_buffer_for_read(&mut self) -> io::Result<()> {
+        if self.input_buffer_start > 0 {
+            self.input_buffer
+                .copy_within(self.input_buffer_start..self.input_buffer_end, 0);
+            self.input_buffer_end -= self.input_buffer_start;
+            self.input_buffer_start = 0;
+        }
+        if self.input_buffer_end >= self.max_buffer_size {
+            return Err(max_buffer_size_error(self.max_buffer_size));
+        }
+        let min_end = min(
+            self.input_buffer_end + MIN_BUFFER_SIZE,
+            self.max_buffer_size,
+        );
+        if self.input_buffer.len() < min_end {
+            self.input_buffer.resize(min_end, 0);
+        }
+        if self.input_buffer.len() < self.input_buffer.capacity() {
+            // We keep extending to have as much space as available without reallocation
+            self.input_buffer.resize(self.input_buffer.capacity(), 0);
+        }
+        Ok(())
+    }
+
+    /// Returns the next event without consuming it.
+    ///
+    /// The event is kept and returned again by the next call to [`parse_next`](Self::parse_next).
+    /// Errors are not kept: they are returned by this method only.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, TokioAsyncReaderJsonParser};
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> ::std::io::Result<()> {
+    /// let mut reader = TokioAsyncReaderJsonParser::new(b"[1]".as_slice());
+    /// assert_eq!(reader.peek_next().await?, &JsonEvent::StartArray);
+    /// assert_eq!(reader.parse_next().await?, JsonEvent::StartArray);
+    /// assert_eq!(reader.peek_next().await?, &JsonEvent::Number("1".into()));
+    /// assert_eq!(reader.parse_next().await?, JsonEvent::Number("1".into()));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn peek_next(&mut self) -> Result<&JsonEvent<'static>, JsonParseError> {
+        if self.parser.buffered_event.is_none() {
+            let event = self.parse_next().await?.into_owned();
+            self.parser.buffer_peeked_event(event);
+        }
+        Ok(self.parser.buffered_event.as_ref().unwrap())
+    }
+
     #[deprecated(note = "Use parse_next() instead")]
     pub async fn read_next_event(&mut self) -> Result<JsonEvent<'_>, JsonParseError> {
         self.parse_next().await
     }
 }
 
+/// A [`Stream`] over the events of a [`TokioAsyncReaderJsonParser`].
+///
+/// Built using [`TokioAsyncReaderJsonParser::into_stream`].
+#[cfg(feature = "async-tokio")]
+pub struct TokioAsyncReaderJsonParserStream<R: AsyncRead + Unpin> {
+    parser: TokioAsyncReaderJsonParser<R>,
+}
+
+#[cfg(feature = "async-tokio")]
+impl<R: AsyncRead + Unpin> Stream for TokioAsyncReaderJsonParserStream<R> {
+    type Item = Result<JsonEvent<'static>, JsonParseError>;
+
+    #[inline]
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(match ready!(self.parser.poll_parse_next_owned(cx)) {
+            Ok(JsonEvent::Eof) => None,
+            result => Some(result),
+        })
+    }
+}
+
 /// Parses a JSON file from a `&[u8]`.
 ///
 /// ```
@@ -261,6 +990,120 @@ impl<'a> SliceJsonParser<'a> {
         }
     }
 
+    /// Returns strings and object keys longer than `size` bytes in chunks.
+    ///
+    /// See [`LowLevelJsonParser::with_string_chunk_size`] for details.
+    #[inline]
+    pub fn with_string_chunk_size(mut self, size: usize) -> Self {
+        self.parser = self.parser.with_string_chunk_size(size);
+        self
+    }
+
+    /// Maximal allowed number of nested object and array openings.
+    ///
+    /// See [`LowLevelJsonParser::with_max_stack_size`] for details.
+    #[inline]
+    pub fn with_max_stack_size(mut self, size: usize) -> Self {
+        self.parser = self.parser.with_max_stack_size(size);
+        self
+    }
+
+    /// Returns an error if an object contains twice the same key.
+    ///
+    /// See [`LowLevelJsonParser::with_duplicate_object_key_detection`] for details.
+    #[inline]
+    pub fn with_duplicate_object_key_detection(mut self) -> Self {
+        self.parser = self.parser.with_duplicate_object_key_detection();
+        self
+    }
+
+    /// Maximal total size in bytes of the object keys kept in memory for duplicate key detection.
+    ///
+    /// See [`LowLevelJsonParser::with_max_tracked_object_keys_size`] for details.
+    #[inline]
+    pub fn with_max_tracked_object_keys_size(mut self, size: usize) -> Self {
+        self.parser = self.parser.with_max_tracked_object_keys_size(size);
+        self
+    }
+
+    /// Validates that the input follows the [I-JSON](https://www.rfc-editor.org/rfc/rfc7493) profile.
+    ///
+    /// See [`LowLevelJsonParser::with_i_json_validation`] for details.
+    #[inline]
+    pub fn with_i_json_validation(mut self) -> Self {
+        self.parser = self.parser.with_i_json_validation();
+        self
+    }
+
+    /// Sets limits on the size of the parsed input to protect against malicious documents.
+    ///
+    /// See [`LowLevelJsonParser::with_limits`] for details.
+    #[inline]
+    pub fn with_limits(mut self, limits: JsonLimits) -> Self {
+        self.parser = self.parser.with_limits(limits);
+        self
+    }
+
+    /// Allows escaped lone UTF-16 surrogates like `"\uD888"` in strings and object keys.
+    ///
+    /// See [`LowLevelJsonParser::with_lone_surrogate_preservation`] for details.
+    #[inline]
+    pub fn with_lone_surrogate_preservation(mut self) -> Self {
+        self.parser = self.parser.with_lone_surrogate_preservation();
+        self
+    }
+
+    /// The lone surrogates replaced by U+FFFD in the string or object key returned by the last call to `parse_next`.
+    ///
+    /// See [`LowLevelJsonParser::lone_surrogates`] for details.
+    #[inline]
+    pub fn lone_surrogates(&self) -> &[LoneSurrogate] {
+        self.parser.lone_surrogates()
+    }
+
+    /// Replaces invalid UTF-8 sequences and lone surrogate escapes in strings and object keys by U+FFFD instead of returning an error.
+    ///
+    /// See [`LowLevelJsonParser::with_lossy_decoding`] for details.
+    #[inline]
+    pub fn with_lossy_decoding(mut self) -> Self {
+        self.parser = self.parser.with_lossy_decoding();
+        self
+    }
+
+    /// The invalid sequences replaced by U+FFFD in the string or object key returned by the last call to `parse_next`.
+    ///
+    /// See [`LowLevelJsonParser::decoding_warnings`] for details.
+    #[inline]
+    pub fn decoding_warnings(&self) -> &[JsonSyntaxError] {
+        self.parser.decoding_warnings()
+    }
+
+    /// Collects the recoverable syntax errors instead of returning them and repairs the returned events.
+    ///
+    /// See [`LowLevelJsonParser::with_error_recovery`] for details.
+    #[inline]
+    pub fn with_error_recovery(mut self) -> Self {
+        self.parser = self.parser.with_error_recovery();
+        self
+    }
+
+    /// Maximal number of errors collected in [error recovery mode](Self::with_error_recovery).
+    ///
+    /// See [`LowLevelJsonParser::with_max_recovered_errors`] for details.
+    #[inline]
+    pub fn with_max_recovered_errors(mut self, count: usize) -> Self {
+        self.parser = self.parser.with_max_recovered_errors(count);
+        self
+    }
+
+    /// The errors collected since the beginning of the parsing in [error recovery mode](Self::with_error_recovery).
+    ///
+    /// See [`LowLevelJsonParser::diagnostics`] for details.
+    #[inline]
+    pub fn diagnostics(&self) -> &[JsonSyntaxError] {
+        self.parser.diagnostics()
+    }
+
     #[inline]
     pub fn parse_next(&mut self) -> Result<JsonEvent<'a>, JsonSyntaxError> {
         loop {
@@ -275,10 +1118,70 @@ impl<'a> SliceJsonParser<'a> {
         }
     }
 
+    /// Returns the next event without consuming it.
+    ///
+    /// The event is kept and returned again by the next call to [`parse_next`](Self::parse_next).
+    /// Errors are not kept: they are returned by this method only.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, SliceJsonParser};
+    ///
+    /// let mut reader = SliceJsonParser::new(b"[1]");
+    /// assert_eq!(reader.peek_next()?, &JsonEvent::StartArray);
+    /// assert_eq!(reader.parse_next()?, JsonEvent::StartArray);
+    /// assert_eq!(reader.peek_next()?, &JsonEvent::Number("1".into()));
+    /// assert_eq!(reader.parse_next()?, JsonEvent::Number("1".into()));
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn peek_next(&mut self) -> Result<&JsonEvent<'static>, JsonSyntaxError> {
+        if self.parser.buffered_event.is_none() {
+            let event = self.parse_next()?.into_owned();
+            self.parser.buffer_peeked_event(event);
+        }
+        Ok(self.parser.buffered_event.as_ref().unwrap())
+    }
+
     #[deprecated(note = "Use parse_next() instead")]
     pub fn read_next_event(&mut self) -> Result<JsonEvent<'_>, JsonSyntaxError> {
         self.parse_next()
     }
+
+    /// The maximal number of nested arrays and objects if set with [`with_max_stack_size`](Self::with_max_stack_size).
+    #[inline]
+    pub(crate) fn max_stack_size(&self) -> Option<usize> {
+        self.parser.max_stack_size()
+    }
+}
+
+/// Iterates on the events until the end of the file.
+///
+/// The [`JsonEvent::Eof`] event is not returned.
+///
+/// ```
+/// use json_event_parser::{JsonEvent, SliceJsonParser};
+///
+/// let events = SliceJsonParser::new(b"{\"foo\": 1}").collect::<Result<Vec<_>, _>>()?;
+/// assert_eq!(
+///     events,
+///     [
+///         JsonEvent::StartObject,
+///         JsonEvent::ObjectKey("foo".into()),
+///         JsonEvent::Number("1".into()),
+///         JsonEvent::EndObject
+///     ]
+/// );
+/// # std::io::Result::Ok(())
+/// ```
+impl<'a> Iterator for SliceJsonParser<'a> {
+    type Item = Result<JsonEvent<'a>, JsonSyntaxError>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.parse_next() {
+            Ok(JsonEvent::Eof) => None,
+            result => Some(result),
+        }
+    }
 }
 
 /// A low-level JSON parser acting on a provided buffer.
@@ -338,9 +1241,25 @@ impl<'a> SliceJsonParser<'a> {
 pub struct LowLevelJsonParser {
     lexer: JsonLexer,
     state_stack: Vec<JsonState>,
-    max_state_stack_size: usize,
+    /// Set if a limit different from [`MAX_STATE_STACK_SIZE`] has been configured
+    max_state_stack_size: Option<usize>,
     element_read: bool,
-    buffered_event: Option<JsonEvent<'static>>,
+    pub(crate) buffered_event: Option<JsonEvent<'static>>,
+    /// Set if `buffered_event` has been returned by a peek and so already went through the limit checks
+    is_buffered_event_peeked: bool,
+    /// Set if duplicate object keys must be detected
+    object_keys: Option<ObjectKeysTracker>,
+    is_i_json: bool,
+    limits: JsonLimits,
+    event_count: u64,
+    /// For each open container if it is an array and its number of elements or members.
+    ///
+    /// Only filled if a limit on them is set.
+    container_sizes: Vec<(bool, usize)>,
+    /// A limit has been exceeded, the parsing is stopped
+    is_limit_exceeded: bool,
+    /// Set if the error recovery mode is enabled
+    recovery: Option<ErrorRecovery>,
 }
 
 impl LowLevelJsonParser {
@@ -353,21 +1272,538 @@ impl LowLevelJsonParser {
                 file_start_of_last_line: 0,
                 file_start_of_last_token: 0,
                 is_start: true,
+                is_in_string: false,
+                string_chunk_size: None,
+                string_length: 0,
+                max_string_length: None,
+                max_number_length: None,
+                lone_surrogates: None,
+                decoding_warnings: None,
             },
             state_stack: Vec::new(),
-            max_state_stack_size: MAX_STATE_STACK_SIZE,
+            max_state_stack_size: None,
             element_read: false,
             buffered_event: None,
+            is_buffered_event_peeked: false,
+            object_keys: None,
+            is_i_json: false,
+            limits: JsonLimits::new(),
+            event_count: 0,
+            container_sizes: Vec::new(),
+            is_limit_exceeded: false,
+            recovery: None,
         }
     }
 
     /// Maximal allowed number of nested object and array openings. Infinite by default.
     #[inline]
     pub fn with_max_stack_size(mut self, size: usize) -> Self {
-        self.max_state_stack_size = size;
+        self.max_state_stack_size = Some(size);
+        self
+    }
+
+    /// Returns strings and object keys longer than `size` bytes in chunks.
+    ///
+    /// The content of such a string is returned as a sequence of [`JsonEvent::StringChunk`] events
+    /// (resp. [`JsonEvent::ObjectKeyChunk`]) of about `size` bytes each,
+    /// followed by a [`JsonEvent::String`] event (resp. [`JsonEvent::ObjectKey`]) with the remaining content.
+    /// Escape sequences are decoded and never split between chunks.
+    /// Disabled by default.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, LowLevelJsonParser, LowLevelJsonParserResult};
+    ///
+    /// let mut reader = LowLevelJsonParser::new().with_string_chunk_size(3);
+    /// let LowLevelJsonParserResult {
+    ///     event,
+    ///     consumed_bytes,
+    /// } = reader.parse_next(b"\"abcd\\ne", false);
+    /// assert_eq!(event.unwrap()?, JsonEvent::StringChunk("abc".into()));
+    /// assert_eq!(consumed_bytes, 4);
+    /// let LowLevelJsonParserResult {
+    ///     event,
+    ///     consumed_bytes,
+    /// } = reader.parse_next(b"d\\ne\"", true);
+    /// assert_eq!(event.unwrap()?, JsonEvent::StringChunk("d\n".into()));
+    /// assert_eq!(consumed_bytes, 3);
+    /// let LowLevelJsonParserResult { event, .. } = reader.parse_next(b"e\"", true);
+    /// assert_eq!(event.unwrap()?, JsonEvent::String("e".into()));
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    #[inline]
+    pub fn with_string_chunk_size(mut self, size: usize) -> Self {
+        self.lexer.string_chunk_size = Some(size.max(1));
         self
     }
 
+    /// Returns an error if an object contains twice the same key. Disabled by default.
+    ///
+    /// The keys of all the open objects are kept in memory to detect duplicates.
+    /// Their total size is bounded by [`with_max_tracked_object_keys_size`](Self::with_max_tracked_object_keys_size).
+    /// The parsing can continue after the error, the duplicate key is returned by the next call.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, LowLevelJsonParser, LowLevelJsonParserResult};
+    ///
+    /// let mut reader = LowLevelJsonParser::new().with_duplicate_object_key_detection();
+    /// let input = br#"{"a": 1, "a": 2}"#;
+    /// let mut position = 0;
+    /// let mut error = None;
+    /// loop {
+    ///     let LowLevelJsonParserResult {
+    ///         event,
+    ///         consumed_bytes,
+    ///     } = reader.parse_next(&input[position..], true);
+    ///     position += consumed_bytes;
+    ///     match event.unwrap() {
+    ///         Ok(JsonEvent::Eof) => break,
+    ///         Ok(_) => (),
+    ///         Err(e) => error = Some(e),
+    ///     }
+    /// }
+    /// assert_eq!(error.unwrap().location().start.offset, 9);
+    /// ```
+    #[inline]
+    pub fn with_duplicate_object_key_detection(mut self) -> Self {
+        if self.object_keys.is_none() {
+            self.object_keys = Some(ObjectKeysTracker::new(MAX_TRACKED_OBJECT_KEYS_SIZE));
+        }
+        self
+    }
+
+    /// Maximal total size in bytes of the object keys kept in memory for [duplicate key detection](Self::with_duplicate_object_key_detection).
+    ///
+    /// The keys of all the currently open objects are counted.
+    /// An error is returned if the limit is exceeded. 16MiB by default.
+    /// Setting it enables duplicate key detection.
+    #[inline]
+    pub fn with_max_tracked_object_keys_size(mut self, size: usize) -> Self {
+        self = self.with_duplicate_object_key_detection();
+        if let Some(object_keys) = &mut self.object_keys {
+            object_keys.max_keys_size = size;
+        }
+        self
+    }
+
+    /// Validates that the input follows the [I-JSON](https://www.rfc-editor.org/rfc/rfc7493) profile. Disabled by default.
+    ///
+    /// On top of the JSON syntax, errors are returned on:
+    /// * duplicate object keys (see [`with_duplicate_object_key_detection`](Self::with_duplicate_object_key_detection)),
+    /// * numbers that are not representable as IEEE 754 double precision numbers
+    ///   and integers outside of the [-(2<sup>53</sup>)+1, 2<sup>53</sup>-1] range,
+    /// * strings and object keys containing Unicode noncharacters,
+    /// * strings and object keys containing lone surrogates or invalid UTF-8,
+    ///   even if [`with_lone_surrogate_preservation`](Self::with_lone_surrogate_preservation)
+    ///   or [`with_lossy_decoding`](Self::with_lossy_decoding) is set.
+    ///
+    /// The parsing can continue after the error, the invalid event is returned by the next call.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, SliceJsonParser};
+    ///
+    /// let mut reader = SliceJsonParser::new(b"[9007199254740991, 9007199254740992]")
+    ///     .with_i_json_validation();
+    /// assert_eq!(reader.parse_next()?, JsonEvent::StartArray);
+    /// assert_eq!(
+    ///     reader.parse_next()?,
+    ///     JsonEvent::Number("9007199254740991".into())
+    /// );
+    /// assert!(reader.parse_next().is_err());
+    /// # std::io::Result::Ok(())
+    /// ```
+    #[inline]
+    pub fn with_i_json_validation(mut self) -> Self {
+        self.is_i_json = true;
+        self.with_duplicate_object_key_detection()
+    }
+
+    /// Sets limits on the size of the parsed input to protect against malicious documents.
+    ///
+    /// When a limit is exceeded, an error is returned and the parsing stops:
+    /// the next calls return [`JsonEvent::Eof`].
+    /// The exceeded limit is given by [`JsonSyntaxError::exceeded_limit`].
+    /// See [`JsonLimits`] for the available limits.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, JsonLimit, JsonLimits, SliceJsonParser};
+    ///
+    /// let mut reader = SliceJsonParser::new(br#"{"a": [1, 2, 3]}"#)
+    ///     .with_limits(JsonLimits::new().with_max_array_elements(2));
+    /// assert_eq!(reader.parse_next()?, JsonEvent::StartObject);
+    /// assert_eq!(reader.parse_next()?, JsonEvent::ObjectKey("a".into()));
+    /// assert_eq!(reader.parse_next()?, JsonEvent::StartArray);
+    /// assert_eq!(reader.parse_next()?, JsonEvent::Number("1".into()));
+    /// assert_eq!(reader.parse_next()?, JsonEvent::Number("2".into()));
+    /// let error = reader.parse_next().unwrap_err();
+    /// assert_eq!(error.exceeded_limit(), Some(JsonLimit::ArrayElements));
+    /// assert_eq!(error.location().start.offset, 13);
+    /// assert_eq!(reader.parse_next()?, JsonEvent::Eof);
+    /// # std::io::Result::Ok(())
+    /// ```
+    #[inline]
+    pub fn with_limits(mut self, limits: JsonLimits) -> Self {
+        self.limits = limits;
+        self.lexer.max_string_length = limits.max_string_length;
+        self.lexer.max_number_length = limits.max_number_length;
+        self
+    }
+
+    /// Allows escaped lone UTF-16 surrogates like `"\uD888"` in strings and object keys. Disabled by default.
+    ///
+    /// They are replaced by U+FFFD (the replacement character) in the returned string
+    /// and their values are returned by [`lone_surrogates`](Self::lone_surrogates).
+    /// [`LowLevelJsonSerializer::serialize_event_with_lone_surrogates`](crate::LowLevelJsonSerializer::serialize_event_with_lone_surrogates)
+    /// allows to write them back as escape sequences, making the round trip lossless.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, LoneSurrogate, SliceJsonParser};
+    ///
+    /// let mut reader =
+    ///     SliceJsonParser::new(br#""a\uD888b\uD83D\uDE00""#).with_lone_surrogate_preservation();
+    /// assert_eq!(reader.parse_next()?, JsonEvent::String("a\u{FFFD}b😀".into()));
+    /// assert_eq!(
+    ///     reader.lone_surrogates(),
+    ///     [LoneSurrogate {
+    ///         offset: 1,
+    ///         code_unit: 0xD888,
+    ///         hex_digits: *b"D888"
+    ///     }]
+    /// );
+    /// # std::io::Result::Ok(())
+    /// ```
+    #[inline]
+    pub fn with_lone_surrogate_preservation(mut self) -> Self {
+        if self.lexer.lone_surrogates.is_none() {
+            self.lexer.lone_surrogates = Some(Vec::new());
+        }
+        self
+    }
+
+    /// The lone surrogates replaced by U+FFFD in the string or object key returned by the last call to [`parse_next`](Self::parse_next).
+    ///
+    /// Always empty if [`with_lone_surrogate_preservation`](Self::with_lone_surrogate_preservation) is not set.
+    #[inline]
+    pub fn lone_surrogates(&self) -> &[LoneSurrogate] {
+        self.lexer.lone_surrogates.as_deref().unwrap_or_default()
+    }
+
+    /// Replaces invalid UTF-8 sequences and lone surrogate escapes in strings and object keys by U+FFFD instead of returning an error. Disabled by default.
+    ///
+    /// The replacements are reported as warnings by [`decoding_warnings`](Self::decoding_warnings).
+    /// If [`with_lone_surrogate_preservation`](Self::with_lone_surrogate_preservation) is set,
+    /// lone surrogates are reported by [`lone_surrogates`](Self::lone_surrogates) instead.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, SliceJsonParser};
+    ///
+    /// let mut reader = SliceJsonParser::new(b"[\"a\xFFb\\uDC00\"]").with_lossy_decoding();
+    /// assert_eq!(reader.parse_next()?, JsonEvent::StartArray);
+    /// assert_eq!(
+    ///     reader.parse_next()?,
+    ///     JsonEvent::String("a\u{FFFD}b\u{FFFD}".into())
+    /// );
+    /// let warnings = reader.decoding_warnings();
+    /// assert_eq!(warnings.len(), 2);
+    /// assert_eq!(warnings[0].location().start.offset, 3);
+    /// assert_eq!(warnings[1].location().start.offset, 5);
+    /// # std::io::Result::Ok(())
+    /// ```
+    #[inline]
+    pub fn with_lossy_decoding(mut self) -> Self {
+        if self.lexer.decoding_warnings.is_none() {
+            self.lexer.decoding_warnings = Some(Vec::new());
+        }
+        self
+    }
+
+    /// The invalid sequences replaced by U+FFFD in the string or object key returned by the last call to [`parse_next`](Self::parse_next).
+    ///
+    /// Always empty if [`with_lossy_decoding`](Self::with_lossy_decoding) is not set.
+    #[inline]
+    pub fn decoding_warnings(&self) -> &[JsonSyntaxError] {
+        self.lexer.decoding_warnings.as_deref().unwrap_or_default()
+    }
+
+    /// Collects the recoverable syntax errors instead of returning them and repairs the returned events. Disabled by default.
+    ///
+    /// With this mode, [`parse_next`](Self::parse_next) returns a well-formed sequence of events:
+    /// it contains at most one root value, arrays and objects are always closed and each object key is followed by a value.
+    /// The errors are collected and are available from [`diagnostics`](Self::diagnostics).
+    ///
+    /// The input is repaired this way:
+    /// - invalid tokens, unexpected commas, colons and closing brackets and misplaced object keys are skipped,
+    /// - missing commas and colons are assumed to be there,
+    /// - values in place of object keys and values after the root value are skipped with their content,
+    /// - missing object values are replaced by `null`,
+    /// - at the end of the file, the unterminated strings, arrays and objects are closed.
+    ///
+    /// Errors caused by [limits](Self::with_limits) are not recoverable: they are still returned by `parse_next` and stop the parsing.
+    /// The parsing is also stopped if more than [`with_max_recovered_errors`](Self::with_max_recovered_errors) errors are found,
+    /// the first error above the maximum being returned by `parse_next`.
+    /// The errors raised by [`with_duplicate_object_key_detection`](Self::with_duplicate_object_key_detection)
+    /// and [`with_i_json_validation`](Self::with_i_json_validation) are collected and the faulty events are kept.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, SliceJsonParser};
+    ///
+    /// let mut reader = SliceJsonParser::new(b"{\"a\": [1 2,], \"b\"").with_error_recovery();
+    /// assert_eq!(reader.parse_next()?, JsonEvent::StartObject);
+    /// assert_eq!(reader.parse_next()?, JsonEvent::ObjectKey("a".into()));
+    /// assert_eq!(reader.parse_next()?, JsonEvent::StartArray);
+    /// assert_eq!(reader.parse_next()?, JsonEvent::Number("1".into()));
+    /// assert_eq!(reader.parse_next()?, JsonEvent::Number("2".into()));
+    /// assert_eq!(reader.parse_next()?, JsonEvent::EndArray);
+    /// assert_eq!(reader.parse_next()?, JsonEvent::ObjectKey("b".into()));
+    /// assert_eq!(reader.parse_next()?, JsonEvent::Null);
+    /// assert_eq!(reader.parse_next()?, JsonEvent::EndObject);
+    /// assert_eq!(reader.parse_next()?, JsonEvent::Eof);
+    /// assert_eq!(reader.diagnostics().len(), 3);
+    /// # std::io::Result::Ok(())
+    /// ```
+    #[inline]
+    pub fn with_error_recovery(mut self) -> Self {
+        if self.recovery.is_none() {
+            self.recovery = Some(ErrorRecovery::new(MAX_RECOVERED_ERRORS));
+        }
+        self
+    }
+
+    /// Maximal number of errors collected in [error recovery mode](Self::with_error_recovery). 100 by default.
+    ///
+    /// Enables the error recovery mode.
+    #[inline]
+    pub fn with_max_recovered_errors(mut self, count: usize) -> Self {
+        self.recovery
+            .get_or_insert_with(|| ErrorRecovery::new(count))
+            .max_errors = count;
+        self
+    }
+
+    /// The errors collected since the beginning of the parsing in [error recovery mode](Self::with_error_recovery).
+    ///
+    /// Always empty if the error recovery mode is not enabled.
+    #[inline]
+    pub fn diagnostics(&self) -> &[JsonSyntaxError] {
+        self.recovery
+            .as_ref()
+            .map_or(&[], |recovery| recovery.diagnostics.as_slice())
+    }
+
+    /// The diagnostics added since the last call to this method.
+    pub(crate) fn new_diagnostics_mut(&mut self) -> &mut [JsonSyntaxError] {
+        let Some(recovery) = &mut self.recovery else {
+            return &mut [];
+        };
+        let start = mem::replace(
+            &mut recovery.reported_diagnostics,
+            recovery.diagnostics.len(),
+        );
+        &mut recovery.diagnostics[start..]
+    }
+
+    /// The position in the input after the bytes consumed so far.
+    #[inline]
+    pub fn position(&self) -> TextPosition {
+        TextPosition {
+            line: self.lexer.file_line,
+            column: self.lexer.file_offset - self.lexer.file_start_of_last_line,
+            offset: self.lexer.file_offset,
+        }
+    }
+
+    /// The maximal number of nested arrays and objects if set with [`with_max_stack_size`](Self::with_max_stack_size).
+    #[inline]
+    pub(crate) fn max_stack_size(&self) -> Option<usize> {
+        self.max_state_stack_size
+    }
+
+    /// Serializes the parsing state into a compact byte snapshot.
+    ///
+    /// The parsing can be resumed later with [`restore_snapshot`](Self::restore_snapshot)
+    /// by giving to the restored parser the input starting at the [`position`](Self::position) offset.
+    ///
+    /// The snapshot contains the position, the open arrays and objects, the buffered event
+    /// and the state used by the [limits](Self::with_limits), the [duplicate key detection](Self::with_duplicate_object_key_detection)
+    /// and the [error recovery](Self::with_error_recovery), including the already collected [diagnostics](Self::diagnostics).
+    /// It does not contain the configuration of the parser.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, LowLevelJsonParser};
+    ///
+    /// let input = b"{\"a\": [1, 2]}";
+    /// let mut parser = LowLevelJsonParser::new();
+    /// let mut offset = 0;
+    /// for _ in 0..3 {
+    ///     offset += parser.parse_next(&input[offset..], true).consumed_bytes;
+    /// }
+    /// let snapshot = parser.snapshot();
+    /// assert_eq!(parser.position().offset, 7);
+    ///
+    /// let mut parser = LowLevelJsonParser::new();
+    /// parser.restore_snapshot(&snapshot)?;
+    /// let result = parser.parse_next(&input[7..], true);
+    /// assert_eq!(result.event.unwrap()?, JsonEvent::Number("1".into()));
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn snapshot(&self) -> Vec<u8> {
+        let mut writer = SnapshotWriter::new();
+        writer.write_u64(self.lexer.file_offset);
+        writer.write_u64(self.lexer.file_line);
+        writer.write_u64(self.lexer.file_start_of_last_line);
+        writer.write_u64(self.lexer.file_start_of_last_token);
+        writer.write_bool(self.lexer.is_start);
+        writer.write_bool(self.lexer.is_in_string);
+        writer.write_usize(self.lexer.string_length);
+        writer.write_usize(self.state_stack.len());
+        for state in &self.state_stack {
+            writer.write_u8(*state as u8);
+        }
+        writer.write_bool(self.element_read);
+        writer.write_bool(self.buffered_event.is_some());
+        if let Some(event) = &self.buffered_event {
+            writer.write_event(event);
+        }
+        writer.write_bool(self.is_buffered_event_peeked);
+        writer.write_u64(self.event_count);
+        writer.write_usize(self.container_sizes.len());
+        for (is_array, size) in &self.container_sizes {
+            writer.write_bool(*is_array);
+            writer.write_usize(*size);
+        }
+        writer.write_bool(self.is_limit_exceeded);
+        writer.write_bool(self.object_keys.is_some());
+        if let Some(object_keys) = &self.object_keys {
+            object_keys.write_snapshot(&mut writer);
+        }
+        writer.write_bool(self.recovery.is_some());
+        if let Some(recovery) = &self.recovery {
+            recovery.write_snapshot(&mut writer);
+        }
+        writer.finish()
+    }
+
+    /// Restores the parsing state from a snapshot built by [`snapshot`](Self::snapshot).
+    ///
+    /// The parser must have the same configuration as the one the snapshot has been taken from.
+    /// The next input given to [`parse_next`](Self::parse_next) must start at the [`position`](Self::position) offset of the snapshot.
+    ///
+    /// An error is returned if the snapshot is invalid or does not match the duplicate key detection or error recovery configuration.
+    /// The parser state is left unchanged in this case.
+    pub fn restore_snapshot(&mut self, snapshot: &[u8]) -> io::Result<()> {
+        let mut reader = SnapshotReader::new(snapshot)?;
+        let file_offset = reader.read_u64()?;
+        let file_line = reader.read_u64()?;
+        let file_start_of_last_line = reader.read_u64()?;
+        let file_start_of_last_token = reader.read_u64()?;
+        let is_start = reader.read_bool()?;
+        let is_in_string = reader.read_bool()?;
+        let string_length = reader.read_usize()?;
+        let state_stack = (0..reader.read_len()?)
+            .map(|_| JsonState::from_u8(reader.read_u8()?))
+            .collect::<io::Result<Vec<_>>>()?;
+        let element_read = reader.read_bool()?;
+        let buffered_event = if reader.read_bool()? {
+            Some(reader.read_event()?)
+        } else {
+            None
+        };
+        let is_buffered_event_peeked = reader.read_bool()?;
+        let event_count = reader.read_u64()?;
+        let container_sizes = (0..reader.read_len()?)
+            .map(|_| Ok((reader.read_bool()?, reader.read_usize()?)))
+            .collect::<io::Result<Vec<_>>>()?;
+        let is_limit_exceeded = reader.read_bool()?;
+        let object_keys = if reader.read_bool()? {
+            let Some(object_keys) = &self.object_keys else {
+                return Err(invalid_snapshot(
+                    "The snapshot has been taken with duplicate object key detection enabled",
+                ));
+            };
+            Some(object_keys.read_snapshot(&mut reader)?)
+        } else if self.object_keys.is_some() {
+            return Err(invalid_snapshot(
+                "The snapshot has been taken without duplicate object key detection",
+            ));
+        } else {
+            None
+        };
+        let recovery = if reader.read_bool()? {
+            let Some(recovery) = &self.recovery else {
+                return Err(invalid_snapshot(
+                    "The snapshot has been taken with error recovery enabled",
+                ));
+            };
+            Some(recovery.read_snapshot(&mut reader)?)
+        } else if self.recovery.is_some() {
+            return Err(invalid_snapshot(
+                "The snapshot has been taken without error recovery",
+            ));
+        } else {
+            None
+        };
+        reader.finish()?;
+
+        self.lexer.file_offset = file_offset;
+        self.lexer.file_line = file_line;
+        self.lexer.file_start_of_last_line = file_start_of_last_line;
+        self.lexer.file_start_of_last_token = file_start_of_last_token;
+        self.lexer.is_start = is_start;
+        self.lexer.is_in_string = is_in_string;
+        self.lexer.string_length = string_length;
+        if let Some(lone_surrogates) = &mut self.lexer.lone_surrogates {
+            lone_surrogates.clear();
+        }
+        if let Some(decoding_warnings) = &mut self.lexer.decoding_warnings {
+            decoding_warnings.clear();
+        }
+        self.state_stack = state_stack;
+        self.element_read = element_read;
+        self.buffered_event = buffered_event;
+        self.is_buffered_event_peeked = is_buffered_event_peeked;
+        self.event_count = event_count;
+        self.container_sizes = container_sizes;
+        self.is_limit_exceeded = is_limit_exceeded;
+        self.object_keys = object_keys;
+        self.recovery = recovery;
+        Ok(())
+    }
+
+    /// Resets the parser to parse a new document.
+    ///
+    /// The position, the open arrays and objects, the [limit](Self::with_limits) counters,
+    /// the keys tracked by the [duplicate key detection](Self::with_duplicate_object_key_detection)
+    /// and the [diagnostics](Self::diagnostics) are cleared.
+    /// The configuration and the allocated memory are kept, allowing to reuse the parser without new allocations.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, LowLevelJsonParser};
+    ///
+    /// let mut parser = LowLevelJsonParser::new().with_max_stack_size(1);
+    /// parser.parse_next(b"[", false);
+    /// parser.reset();
+    /// assert_eq!(
+    ///     parser.parse_next(b"{}", true).event.unwrap()?,
+    ///     JsonEvent::StartObject
+    /// );
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn reset(&mut self) {
+        self.lexer.reset();
+        self.state_stack.clear();
+        self.element_read = false;
+        self.buffered_event = None;
+        self.is_buffered_event_peeked = false;
+        self.event_count = 0;
+        self.container_sizes.clear();
+        self.is_limit_exceeded = false;
+        if let Some(object_keys) = &mut self.object_keys {
+            object_keys.reset();
+        }
+        if let Some(recovery) = &mut self.recovery {
+            recovery.reset();
+        }
+    }
+
     /// Reads a new event from the data in `input_buffer`.
     ///
     /// `is_ending` must be set to true if all the JSON data have been already consumed or are in `input_buffer`.
@@ -376,6 +1812,234 @@ impl LowLevelJsonParser {
         &mut self,
         input_buffer: &'a [u8],
         is_ending: bool,
+    ) -> LowLevelJsonParserResult<'a> {
+        if self.recovery.is_some() {
+            self.parse_next_recovering(input_buffer, is_ending)
+        } else {
+            self.parse_next_with_limits(input_buffer, is_ending)
+        }
+    }
+
+    fn parse_next_recovering<'a>(
+        &mut self,
+        input_buffer: &'a [u8],
+        is_ending: bool,
+    ) -> LowLevelJsonParserResult<'a> {
+        if let Some(event) = self.buffered_event.take() {
+            // Event already repaired and buffered by a peek
+            self.is_buffered_event_peeked = false;
+            return LowLevelJsonParserResult {
+                consumed_bytes: 0,
+                event: Some(Ok(event)),
+            };
+        }
+        let mut consumed_bytes = 0;
+        loop {
+            let Some(recovery) = &mut self.recovery else {
+                unreachable!()
+            };
+            if recovery.is_finished {
+                return LowLevelJsonParserResult {
+                    consumed_bytes,
+                    event: Some(Ok(JsonEvent::Eof)),
+                };
+            }
+            let event = if let Some(event) = recovery.next_events.pop() {
+                event
+            } else {
+                let result =
+                    self.parse_next_with_limits(&input_buffer[consumed_bytes..], is_ending);
+                consumed_bytes += result.consumed_bytes;
+                let Some(recovery) = &mut self.recovery else {
+                    unreachable!()
+                };
+                match result.event {
+                    None => {
+                        return LowLevelJsonParserResult {
+                            consumed_bytes,
+                            event: None,
+                        }
+                    }
+                    Some(Ok(event)) => event,
+                    Some(Err(error)) => {
+                        if error.exceeded_limit.is_some()
+                            || recovery.diagnostics.len() >= recovery.max_errors
+                        {
+                            // The parsing is stopped
+                            self.is_limit_exceeded = true;
+                            self.buffered_event = None;
+                            recovery.is_finished = true;
+                            return LowLevelJsonParserResult {
+                                consumed_bytes,
+                                event: Some(Err(error)),
+                            };
+                        }
+                        recovery.diagnostics.push(error);
+                        continue;
+                    }
+                }
+            };
+            let Some(recovery) = &mut self.recovery else {
+                unreachable!()
+            };
+            if let Some(event) = recovery.repair(event) {
+                if event == JsonEvent::Eof {
+                    recovery.is_finished = true;
+                }
+                return LowLevelJsonParserResult {
+                    consumed_bytes,
+                    event: Some(Ok(event)),
+                };
+            }
+        }
+    }
+
+    fn parse_next_with_limits<'a>(
+        &mut self,
+        input_buffer: &'a [u8],
+        is_ending: bool,
+    ) -> LowLevelJsonParserResult<'a> {
+        if self.is_limit_exceeded {
+            return LowLevelJsonParserResult {
+                consumed_bytes: 0,
+                event: Some(Ok(JsonEvent::Eof)),
+            };
+        }
+        // A peeked event has already been counted when it has been read
+        let is_peeked = mem::take(&mut self.is_buffered_event_peeked);
+        let result = self.parse_next_unbounded(input_buffer, is_ending);
+        let error = match &result.event {
+            Some(Ok(_)) if is_peeked => None,
+            Some(Ok(event)) => self.check_limits(event),
+            Some(Err(error)) => {
+                if error.exceeded_limit.is_some() {
+                    self.is_limit_exceeded = true;
+                }
+                None
+            }
+            None => self.check_document_size(),
+        };
+        if let Some(error) = error {
+            self.is_limit_exceeded = true;
+            return LowLevelJsonParserResult {
+                consumed_bytes: result.consumed_bytes,
+                event: Some(Err(error)),
+            };
+        }
+        result
+    }
+
+    /// Buffers an event returned by [`parse_next`](Self::parse_next) to return it again on the next call
+    pub(crate) fn buffer_peeked_event(&mut self, event: JsonEvent<'static>) {
+        self.buffered_event = Some(event);
+        self.is_buffered_event_peeked = true;
+    }
+
+    /// If the next call to [`parse_next`](Self::parse_next) reads tokens from the input instead of returning a buffered event
+    pub(crate) fn reads_tokens(&self) -> bool {
+        self.buffered_event.is_none() && !self.is_limit_exceeded
+    }
+
+    /// Returns an error if the event exceeds the limits not handled by the lexer
+    fn check_limits(&mut self, event: &JsonEvent<'_>) -> Option<JsonSyntaxError> {
+        if let Some(error) = self.check_document_size() {
+            return Some(error);
+        }
+        if *event == JsonEvent::Eof {
+            return None;
+        }
+        self.event_count += 1;
+        if let Some(max_event_count) = self.limits.max_event_count {
+            if self.event_count > max_event_count {
+                return Some(self.lexer.limit_error(
+                    JsonLimit::EventCount,
+                    format!("The document contains more than {max_event_count} events"),
+                ));
+            }
+        }
+        if self.limits.max_array_elements.is_none() && self.limits.max_object_members.is_none() {
+            return None;
+        }
+        match event {
+            JsonEvent::ObjectKey(_) => {
+                if let Some((false, size)) = self.container_sizes.last_mut() {
+                    *size += 1;
+                    if let Some(max_object_members) = self.limits.max_object_members {
+                        if *size > max_object_members {
+                            return Some(self.lexer.limit_error(
+                                JsonLimit::ObjectMembers,
+                                format!(
+                                    "The object contains more than {max_object_members} members"
+                                ),
+                            ));
+                        }
+                    }
+                }
+            }
+            JsonEvent::EndArray | JsonEvent::EndObject => {
+                self.container_sizes.pop();
+            }
+            JsonEvent::StringChunk(_) | JsonEvent::ObjectKeyChunk(_) | JsonEvent::Eof => (),
+            _ => {
+                if let Some((true, size)) = self.container_sizes.last_mut() {
+                    *size += 1;
+                    if let Some(max_array_elements) = self.limits.max_array_elements {
+                        if *size > max_array_elements {
+                            return Some(self.lexer.limit_error(
+                                JsonLimit::ArrayElements,
+                                format!(
+                                    "The array contains more than {max_array_elements} elements"
+                                ),
+                            ));
+                        }
+                    }
+                }
+                match event {
+                    JsonEvent::StartArray => self.container_sizes.push((true, 0)),
+                    JsonEvent::StartObject => self.container_sizes.push((false, 0)),
+                    _ => (),
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns an error if the last read string contained lone surrogates or invalid UTF-8, I-JSON only allows valid Unicode
+    fn check_i_json_decoding(&self) -> Option<JsonSyntaxError> {
+        if let Some(lone_surrogate) = self.lone_surrogates().first() {
+            return Some(self.lexer.syntax_error(
+                self.lexer.file_start_of_last_token..self.lexer.file_offset,
+                format!(
+                    "\\u{:X} is a lone surrogate, it is not allowed by I-JSON",
+                    lone_surrogate.code_unit
+                ),
+            ));
+        }
+        let warning = self.decoding_warnings().first()?;
+        Some(JsonSyntaxError {
+            location: warning.location.clone(),
+            message: "Invalid UTF-8 sequences and lone surrogates are not allowed by I-JSON".into(),
+            exceeded_limit: None,
+        })
+    }
+
+    fn check_document_size(&self) -> Option<JsonSyntaxError> {
+        let max_document_size = self.limits.max_document_size?;
+        (self.lexer.file_offset > max_document_size).then(|| {
+            let mut error = self.lexer.syntax_error(
+                max_document_size..self.lexer.file_offset,
+                format!("The document is bigger than {max_document_size} bytes"),
+            );
+            error.exceeded_limit = Some(JsonLimit::DocumentSize);
+            error
+        })
+    }
+
+    #[inline]
+    fn parse_next_unbounded<'a>(
+        &mut self,
+        input_buffer: &'a [u8],
+        is_ending: bool,
     ) -> LowLevelJsonParserResult<'a> {
         if let Some(event) = self.buffered_event.take() {
             return LowLevelJsonParserResult {
@@ -394,14 +2058,34 @@ impl LowLevelJsonParser {
             match token {
                 Ok(token) => {
                     let (event, error) = self.apply_new_token(token);
-                    let error = error.map(|e| {
+                    let mut error = error.map(|e| {
                         self.lexer.syntax_error(
                             self.lexer.file_start_of_last_token..self.lexer.file_offset,
                             e,
                         )
                     });
+                    if let (true, Some(event), None) = (self.is_i_json, &event, &error) {
+                        if let Err(e) = check_i_json_event(event) {
+                            error = Some(self.lexer.syntax_error(
+                                self.lexer.file_start_of_last_token..self.lexer.file_offset,
+                                e,
+                            ));
+                        } else {
+                            error = self.check_i_json_decoding();
+                        }
+                    }
+                    if let (Some(object_keys), Some(event)) = (&mut self.object_keys, &event) {
+                        if let Err((start, e)) =
+                            object_keys.on_event(event, self.lexer.file_start_of_last_token)
+                        {
+                            if error.is_none() {
+                                error =
+                                    Some(self.lexer.syntax_error(start..self.lexer.file_offset, e));
+                            }
+                        }
+                    }
                     if let Some(error) = error {
-                        self.buffered_event = event.map(owned_event);
+                        self.buffered_event = event.map(JsonEvent::into_owned);
                         return LowLevelJsonParserResult {
                             consumed_bytes,
                             event: Some(Err(error)),
@@ -452,6 +2136,19 @@ impl LowLevelJsonParser {
         &mut self,
         token: JsonToken<'a>,
     ) -> (Option<JsonEvent<'a>>, Option<String>) {
+        if let JsonToken::StringChunk(chunk) = token {
+            // The state is only updated by the token ending the string
+            return (
+                match self.state_stack.last() {
+                    Some(JsonState::ObjectKeyOrEnd | JsonState::ObjectKey) => {
+                        Some(JsonEvent::ObjectKeyChunk(chunk))
+                    }
+                    None if self.element_read => None,
+                    _ => Some(JsonEvent::StringChunk(chunk)),
+                },
+                None,
+            );
+        }
         match self.state_stack.pop() {
             Some(JsonState::ObjectKeyOrEnd) => {
                 if token == JsonToken::ClosingCurlyBracket {
@@ -498,7 +2195,12 @@ impl LowLevelJsonParser {
                     (None, self.push_state_stack(JsonState::ObjectKey).err())
                 }
                 JsonToken::ClosingCurlyBracket => (Some(JsonEvent::EndObject), None),
-                _ => (None, Some("Object values must be followed by a comma to add a new value or a curly bracket to end the object".into())),
+                JsonToken::Eof => (None, Some("Object values must be followed by a comma to add a new value or a curly bracket to end the object".into())),
+                _ => {
+                    let _ = self.push_state_stack(JsonState::ObjectKey); // We already have an error
+                    let (event, _) = self.apply_new_token(token);
+                    (event, Some("Object values must be followed by a comma to add a new value or a curly bracket to end the object".into()))
+                }
             },
             Some(JsonState::ArrayValueOrEnd) =>{
                 if token == JsonToken::ClosingSquareBracket {
@@ -567,6 +2269,7 @@ impl LowLevelJsonParser {
             JsonToken::Comma => (None, Some("Unexpected comma, no values to separate".into())),
             JsonToken::Colon => (None, Some("Unexpected colon, no key to follow".into())),
             JsonToken::String(string) => (Some(JsonEvent::String(string)), None),
+            JsonToken::StringChunk(chunk) => (Some(JsonEvent::StringChunk(chunk)), None),
             JsonToken::Number(number) => (Some(JsonEvent::Number(number)), None),
             JsonToken::True => (Some(JsonEvent::Boolean(true)), None),
             JsonToken::False => (Some(JsonEvent::Boolean(false)), None),
@@ -587,10 +2290,10 @@ impl LowLevelJsonParser {
 
     #[inline]
     fn check_stack_size(&self) -> Result<(), String> {
-        if self.state_stack.len() > self.max_state_stack_size {
+        let max_state_stack_size = self.max_state_stack_size.unwrap_or(MAX_STATE_STACK_SIZE);
+        if self.state_stack.len() > max_state_stack_size {
             Err(format!(
-                "Max stack size of {} reached on an object opening",
-                self.max_state_stack_size
+                "Max stack size of {max_state_stack_size} reached on an object opening"
             ))
         } else {
             Ok(())
@@ -604,6 +2307,402 @@ impl Default for LowLevelJsonParser {
     }
 }
 
+/// State of the error recovery mode
+///
+/// It tracks the structure of the returned events to keep it well-formed.
+struct ErrorRecovery {
+    diagnostics: Vec<JsonSyntaxError>,
+    /// Number of diagnostics already returned by [`LowLevelJsonParser::new_diagnostics_mut`]
+    reported_diagnostics: usize,
+    max_errors: usize,
+    /// The containers open in the returned events, `true` for arrays and `false` for objects
+    open_containers: Vec<bool>,
+    /// An object key has been returned without its value yet
+    is_value_expected: bool,
+    /// A string or an object key is being returned in chunks
+    is_in_chunks: bool,
+    is_root_read: bool,
+    /// Number of open arrays and objects in a value that is being skipped
+    skipped_depth: usize,
+    /// Events to repair before reading new ones, in reverse order
+    next_events: Vec<JsonEvent<'static>>,
+    is_finished: bool,
+}
+
+impl ErrorRecovery {
+    const fn new(max_errors: usize) -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            reported_diagnostics: 0,
+            max_errors,
+            open_containers: Vec::new(),
+            is_value_expected: false,
+            is_in_chunks: false,
+            is_root_read: false,
+            skipped_depth: 0,
+            next_events: Vec::new(),
+            is_finished: false,
+        }
+    }
+
+    /// Returns the event to return instead of `event` if any
+    fn repair<'a>(&mut self, event: JsonEvent<'a>) -> Option<JsonEvent<'a>> {
+        if self.skipped_depth > 0 {
+            match event {
+                JsonEvent::StartArray | JsonEvent::StartObject => self.skipped_depth += 1,
+                JsonEvent::EndArray | JsonEvent::EndObject => self.skipped_depth -= 1,
+                JsonEvent::Eof => {
+                    self.skipped_depth = 0;
+                    return self.repair(event);
+                }
+                _ => (),
+            }
+            return None;
+        }
+        if self.is_in_chunks {
+            return match event {
+                JsonEvent::StringChunk(_) | JsonEvent::ObjectKeyChunk(_) => Some(event),
+                JsonEvent::String(_) | JsonEvent::ObjectKey(_) => {
+                    self.is_in_chunks = false;
+                    self.on_value_or_key(event)
+                }
+                _ => {
+                    // We close the string or the key
+                    self.is_in_chunks = false;
+                    self.next_events.push(event.into_owned());
+                    self.on_value_or_key(if self.is_object_key_expected() {
+                        JsonEvent::ObjectKey("".into())
+                    } else {
+                        JsonEvent::String("".into())
+                    })
+                }
+            };
+        }
+        match event {
+            JsonEvent::Eof => {
+                if self.is_value_expected {
+                    self.is_value_expected = false;
+                    self.next_events.push(JsonEvent::Eof);
+                    return Some(JsonEvent::Null);
+                }
+                let end = match self.open_containers.pop() {
+                    Some(true) => JsonEvent::EndArray,
+                    Some(false) => JsonEvent::EndObject,
+                    None => return Some(JsonEvent::Eof),
+                };
+                self.next_events.push(JsonEvent::Eof);
+                Some(end)
+            }
+            JsonEvent::EndArray | JsonEvent::EndObject => {
+                let is_array = event == JsonEvent::EndArray;
+                if self.open_containers.last() != Some(&is_array) {
+                    return None;
+                }
+                if self.is_value_expected {
+                    self.is_value_expected = false;
+                    self.next_events.push(event.into_owned());
+                    return Some(JsonEvent::Null);
+                }
+                self.open_containers.pop();
+                Some(event)
+            }
+            JsonEvent::StringChunk(_) | JsonEvent::ObjectKeyChunk(_) => {
+                if self.is_object_key_expected() != matches!(event, JsonEvent::ObjectKeyChunk(_))
+                    || self.is_after_root()
+                {
+                    return None;
+                }
+                self.is_in_chunks = true;
+                Some(event)
+            }
+            JsonEvent::ObjectKey(_) if self.is_value_expected => {
+                self.is_value_expected = false;
+                self.next_events.push(event.into_owned());
+                Some(JsonEvent::Null)
+            }
+            _ => self.on_value_or_key(event),
+        }
+    }
+
+    /// Handles a complete value start or object key
+    fn on_value_or_key<'a>(&mut self, event: JsonEvent<'a>) -> Option<JsonEvent<'a>> {
+        let is_key = matches!(event, JsonEvent::ObjectKey(_));
+        if self.is_object_key_expected() != is_key || self.is_after_root() {
+            // We skip the value with its content
+            if matches!(event, JsonEvent::StartArray | JsonEvent::StartObject) {
+                self.skipped_depth = 1;
+            }
+            return None;
+        }
+        if is_key {
+            self.is_value_expected = true;
+            return Some(event);
+        }
+        self.is_value_expected = false;
+        self.is_root_read = true;
+        match event {
+            JsonEvent::StartArray => self.open_containers.push(true),
+            JsonEvent::StartObject => self.open_containers.push(false),
+            _ => (),
+        }
+        Some(event)
+    }
+
+    fn reset(&mut self) {
+        self.diagnostics.clear();
+        self.reported_diagnostics = 0;
+        self.open_containers.clear();
+        self.is_value_expected = false;
+        self.is_in_chunks = false;
+        self.is_root_read = false;
+        self.skipped_depth = 0;
+        self.next_events.clear();
+        self.is_finished = false;
+    }
+
+    fn is_after_root(&self) -> bool {
+        self.open_containers.is_empty() && self.is_root_read
+    }
+
+    fn is_object_key_expected(&self) -> bool {
+        self.open_containers.last() == Some(&false) && !self.is_value_expected
+    }
+
+    fn write_snapshot(&self, writer: &mut SnapshotWriter) {
+        writer.write_usize(self.diagnostics.len());
+        for diagnostic in &self.diagnostics {
+            writer.write_position(diagnostic.location.start);
+            writer.write_position(diagnostic.location.end);
+            writer.write_str(&diagnostic.message);
+        }
+        writer.write_usize(self.open_containers.len());
+        for is_array in &self.open_containers {
+            writer.write_bool(*is_array);
+        }
+        writer.write_bool(self.is_value_expected);
+        writer.write_bool(self.is_in_chunks);
+        writer.write_bool(self.is_root_read);
+        writer.write_usize(self.skipped_depth);
+        writer.write_usize(self.next_events.len());
+        for event in &self.next_events {
+            writer.write_event(event);
+        }
+        writer.write_bool(self.is_finished);
+    }
+
+    /// Builds a recovery state with the configuration of this one and the state from the snapshot
+    fn read_snapshot(&self, reader: &mut SnapshotReader<'_>) -> io::Result<Self> {
+        let diagnostics = (0..reader.read_len()?)
+            .map(|_| {
+                Ok(JsonSyntaxError {
+                    location: reader.read_position()?..reader.read_position()?,
+                    message: reader.read_string()?,
+                    exceeded_limit: None,
+                })
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+        Ok(Self {
+            // The diagnostics have already been reported before the snapshot
+            reported_diagnostics: diagnostics.len(),
+            diagnostics,
+            max_errors: self.max_errors,
+            open_containers: (0..reader.read_len()?)
+                .map(|_| reader.read_bool())
+                .collect::<io::Result<_>>()?,
+            is_value_expected: reader.read_bool()?,
+            is_in_chunks: reader.read_bool()?,
+            is_root_read: reader.read_bool()?,
+            skipped_depth: reader.read_usize()?,
+            next_events: (0..reader.read_len()?)
+                .map(|_| reader.read_event())
+                .collect::<io::Result<_>>()?,
+            is_finished: reader.read_bool()?,
+        })
+    }
+}
+
+/// Keys of the open objects kept to detect duplicates
+pub(crate) struct ObjectKeysTracker {
+    open_objects: Vec<HashSet<String>>,
+    /// Total size of the keys in `open_objects`
+    keys_size: usize,
+    max_keys_size: usize,
+    /// The beginning of the current key if returned in chunks
+    key_chunks: String,
+    /// The file offset of the current key start
+    key_start: u64,
+}
+
+impl ObjectKeysTracker {
+    pub(crate) const fn new(max_keys_size: usize) -> Self {
+        Self {
+            open_objects: Vec::new(),
+            keys_size: 0,
+            max_keys_size,
+            key_chunks: String::new(),
+            key_start: 0,
+        }
+    }
+
+    /// Returns the start of the invalid key and the error message if the event is a duplicate key
+    pub(crate) fn on_event(
+        &mut self,
+        event: &JsonEvent<'_>,
+        token_start: u64,
+    ) -> Result<(), (u64, String)> {
+        match event {
+            JsonEvent::StartObject => self.open_objects.push(HashSet::new()),
+            JsonEvent::EndObject => {
+                if let Some(keys) = self.open_objects.pop() {
+                    self.keys_size -= keys.iter().map(String::len).sum::<usize>();
+                }
+            }
+            JsonEvent::ObjectKeyChunk(chunk) => {
+                if self.key_chunks.is_empty() {
+                    self.key_start = token_start;
+                }
+                if self.keys_size + self.key_chunks.len() + chunk.len() > self.max_keys_size {
+                    return Err((self.key_start, self.size_error()));
+                }
+                self.key_chunks.push_str(chunk);
+            }
+            JsonEvent::ObjectKey(key) => {
+                let key_start = if self.key_chunks.is_empty() {
+                    token_start
+                } else {
+                    self.key_start
+                };
+                let key = if self.key_chunks.is_empty() {
+                    Cow::Borrowed(key.as_ref())
+                } else {
+                    let mut full_key = mem::take(&mut self.key_chunks);
+                    full_key.push_str(key);
+                    Cow::Owned(full_key)
+                };
+                let Some(keys) = self.open_objects.last_mut() else {
+                    return Ok(());
+                };
+                if keys.contains(key.as_ref()) {
+                    return Err((
+                        key_start,
+                        format!("The key {key:?} is already used in this object"),
+                    ));
+                }
+                if self.keys_size + key.len() > self.max_keys_size {
+                    return Err((key_start, self.size_error()));
+                }
+                self.keys_size += key.len();
+                keys.insert(key.into_owned());
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.open_objects.clear();
+        self.keys_size = 0;
+        self.key_chunks.clear();
+        self.key_start = 0;
+    }
+
+    fn size_error(&self) -> String {
+        format!(
+            "The object keys are bigger than {} bytes, the maximal size allowed to detect duplicate keys",
+            self.max_keys_size
+        )
+    }
+
+    fn write_snapshot(&self, writer: &mut SnapshotWriter) {
+        writer.write_usize(self.open_objects.len());
+        for keys in &self.open_objects {
+            writer.write_usize(keys.len());
+            for key in keys {
+                writer.write_str(key);
+            }
+        }
+        writer.write_str(&self.key_chunks);
+        writer.write_u64(self.key_start);
+    }
+
+    /// Builds a tracker with the configuration of this one and the state from the snapshot
+    fn read_snapshot(&self, reader: &mut SnapshotReader<'_>) -> io::Result<Self> {
+        let open_objects = (0..reader.read_len()?)
+            .map(|_| {
+                (0..reader.read_len()?)
+                    .map(|_| reader.read_string())
+                    .collect::<io::Result<HashSet<_>>>()
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+        Ok(Self {
+            keys_size: open_objects
+                .iter()
+                .flat_map(|keys| keys.iter().map(String::len))
+                .sum(),
+            open_objects,
+            max_keys_size: self.max_keys_size,
+            key_chunks: reader.read_string()?,
+            key_start: reader.read_u64()?,
+        })
+    }
+}
+
+/// Checks that the event content is allowed by [I-JSON](https://www.rfc-editor.org/rfc/rfc7493)
+pub(crate) fn check_i_json_event(event: &JsonEvent<'_>) -> Result<(), String> {
+    match event {
+        JsonEvent::Number(number) => check_i_json_number(number),
+        JsonEvent::String(string)
+        | JsonEvent::StringChunk(string)
+        | JsonEvent::ObjectKey(string)
+        | JsonEvent::ObjectKeyChunk(string) => check_i_json_string(string),
+        _ => Ok(()),
+    }
+}
+
+fn check_i_json_number(number: &str) -> Result<(), String> {
+    if !number.contains(['.', 'e', 'E']) {
+        let is_in_range = number
+            .strip_prefix('-')
+            .unwrap_or(number)
+            .parse::<u64>()
+            .is_ok_and(|value| value <= MAX_I_JSON_INTEGER);
+        return if is_in_range {
+            Ok(())
+        } else {
+            Err(format!(
+                "The integer {number} is outside of the [-(2^53)+1, 2^53-1] range allowed by I-JSON"
+            ))
+        };
+    }
+    let Ok(value) = number.parse::<f64>() else {
+        return Err(format!("{number} is not a valid number"));
+    };
+    let is_underflow = value == 0.
+        && number
+            .split(['e', 'E'])
+            .next()
+            .is_some_and(|mantissa| mantissa.contains(|c: char| matches!(c, '1'..='9')));
+    if value.is_infinite() || is_underflow {
+        return Err(format!(
+            "The number {number} is outside of the IEEE 754 double precision range allowed by I-JSON"
+        ));
+    }
+    Ok(())
+}
+
+fn check_i_json_string(string: &str) -> Result<(), String> {
+    if let Some(c) = string.chars().find(|c| {
+        let c = u32::from(*c);
+        (0xFDD0..=0xFDEF).contains(&c) || c & 0xFFFE == 0xFFFE
+    }) {
+        return Err(format!(
+            "The noncharacter U+{:04X} is not allowed by I-JSON",
+            u32::from(c)
+        ));
+    }
+    Ok(())
+}
+
 #[derive(Eq, PartialEq, Copy, Clone, Debug)]
 enum JsonState {
     ObjectKey,
@@ -616,20 +2715,38 @@ enum JsonState {
     ArrayCommaOrEnd,
 }
 
+impl JsonState {
+    /// Inverse of the `as u8` conversion used in snapshots
+    fn from_u8(value: u8) -> io::Result<Self> {
+        Ok(match value {
+            0 => Self::ObjectKey,
+            1 => Self::ObjectKeyOrEnd,
+            2 => Self::ObjectColon,
+            3 => Self::ObjectValue,
+            4 => Self::ObjectCommaOrEnd,
+            5 => Self::ArrayValue,
+            6 => Self::ArrayValueOrEnd,
+            7 => Self::ArrayCommaOrEnd,
+            _ => return Err(invalid_snapshot("Invalid parser state")),
+        })
+    }
+}
+
 #[derive(Eq, PartialEq, Clone, Debug)]
 enum JsonToken<'a> {
-    OpeningSquareBracket, // [
-    ClosingSquareBracket, // ]
-    OpeningCurlyBracket,  // {
-    ClosingCurlyBracket,  // }
-    Comma,                // ,
-    Colon,                // :
-    String(Cow<'a, str>), // "..."
-    Number(Cow<'a, str>), // 1.2e3
-    True,                 // true
-    False,                // false
-    Null,                 // null
-    Eof,                  // EOF
+    OpeningSquareBracket,      // [
+    ClosingSquareBracket,      // ]
+    OpeningCurlyBracket,       // {
+    ClosingCurlyBracket,       // }
+    Comma,                     // ,
+    Colon,                     // :
+    String(Cow<'a, str>),      // "..."
+    StringChunk(Cow<'a, str>), // a part of a string
+    Number(Cow<'a, str>),      // 1.2e3
+    True,                      // true
+    False,                     // false
+    Null,                      // null
+    Eof,                       // EOF
 }
 
 struct JsonLexer {
@@ -638,15 +2755,54 @@ struct JsonLexer {
     file_start_of_last_line: u64,
     file_start_of_last_token: u64,
     is_start: bool,
+    /// A string has been partially returned as a chunk and its reading must continue
+    is_in_string: bool,
+    string_chunk_size: Option<usize>,
+    /// Length of the current string content already returned in chunks
+    string_length: usize,
+    max_string_length: Option<usize>,
+    max_number_length: Option<usize>,
+    /// Set if lone surrogates are allowed, contains the ones of the last read string
+    lone_surrogates: Option<Vec<LoneSurrogate>>,
+    /// Set in lossy mode, contains the invalid sequences replaced in the last read string
+    decoding_warnings: Option<Vec<JsonSyntaxError>>,
 }
 
 impl JsonLexer {
+    /// Goes back to the beginning of the input, keeping the configuration
+    fn reset(&mut self) {
+        self.file_offset = 0;
+        self.file_line = 0;
+        self.file_start_of_last_line = 0;
+        self.file_start_of_last_token = 0;
+        self.is_start = true;
+        self.is_in_string = false;
+        self.string_length = 0;
+        if let Some(lone_surrogates) = &mut self.lone_surrogates {
+            lone_surrogates.clear();
+        }
+        if let Some(decoding_warnings) = &mut self.decoding_warnings {
+            decoding_warnings.clear();
+        }
+    }
+
     #[inline]
     fn read_next_token<'a>(
         &mut self,
         mut input_buffer: &'a [u8],
         is_ending: bool,
     ) -> Option<Result<JsonToken<'a>, JsonSyntaxError>> {
+        if let Some(lone_surrogates) = &mut self.lone_surrogates {
+            lone_surrogates.clear();
+        }
+        if let Some(decoding_warnings) = &mut self.decoding_warnings {
+            decoding_warnings.clear();
+        }
+        if self.is_in_string {
+            self.file_start_of_last_token = self.file_offset;
+            return self.read_string(input_buffer, 0);
+        }
+
         // We remove BOM at the beginning
         if self.is_start {
             if input_buffer.len() < 3 && !is_ending {
@@ -725,11 +2881,32 @@ impl JsonLexer {
                 self.file_offset += 1;
                 Some(Ok(JsonToken::Colon))
             }
-            b'"' => self.read_string(input_buffer),
+            b'"' => {
+                self.string_length = 0;
+                self.read_string(input_buffer, 1)
+            }
             b't' => self.read_constant(input_buffer, is_ending, "true", JsonToken::True),
             b'f' => self.read_constant(input_buffer, is_ending, "false", JsonToken::False),
             b'n' => self.read_constant(input_buffer, is_ending, "null", JsonToken::Null),
-            b'-' | b'0'..=b'9' => self.read_number(input_buffer, is_ending),
+            b'-' | b'0'..=b'9' => {
+                let result = self.read_number(input_buffer, is_ending);
+                let Some(max_number_length) = self.max_number_length else {
+                    return result;
+                };
+                let length = match &result {
+                    Some(Ok(JsonToken::Number(number))) => number.len(),
+                    Some(_) => 0,
+                    // The number continues after the end of the buffer
+                    None => input_buffer.len(),
+                };
+                if length > max_number_length {
+                    return Some(Err(self.limit_error(
+                        JsonLimit::NumberLength,
+                        format!("The number is longer than {max_number_length} bytes"),
+                    )));
+                }
+                result
+            }
             c => {
                 self.file_offset += 1;
                 Some(Err(self.syntax_error(
@@ -744,50 +2921,91 @@ impl JsonLexer {
         }
     }
 
-    #[inline]
+    /// Reads a string content starting at `content_start` in `input_buffer` and checks its length.
     fn read_string<'a>(
         &mut self,
         input_buffer: &'a [u8],
+        content_start: usize,
+    ) -> Option<Result<JsonToken<'a>, JsonSyntaxError>> {
+        let start_file_offset = self.file_offset;
+        let result = self.read_string_content(input_buffer, content_start);
+        let Some(max_string_length) = self.max_string_length else {
+            return result;
+        };
+        let length = self.string_length
+            + if result.is_some() {
+                usize::try_from(self.file_offset - start_file_offset).unwrap()
+                    - content_start
+                    - usize::from(!self.is_in_string) // The closing quote
+            } else {
+                // The string continues after the end of the buffer
+                input_buffer.len() - content_start
+            };
+        if length > max_string_length {
+            return Some(Err(self.limit_error(
+                JsonLimit::StringLength,
+                format!("The string is longer than {max_string_length} bytes"),
+            )));
+        }
+        if result.is_some() {
+            self.string_length = length;
+        }
+        result
+    }
+
+    #[inline]
+    /// Reads a string content starting at `content_start` in `input_buffer`.
+    ///
+    /// If the string is longer than the chunk size, only a chunk is returned and [`is_in_string`](Self::is_in_string) is set.
+    fn read_string_content<'a>(
+        &mut self,
+        input_buffer: &'a [u8],
+        content_start: usize,
     ) -> Option<Result<JsonToken<'a>, JsonSyntaxError>> {
+        // The string might have already been partially read
+        if let Some(lone_surrogates) = &mut self.lone_surrogates {
+            lone_surrogates.clear();
+        }
+        if let Some(decoding_warnings) = &mut self.decoding_warnings {
+            decoding_warnings.clear();
+        }
         let mut error = None;
         let mut string: Option<(String, usize)> = None;
-        let mut next_byte_offset = 1;
+        let mut next_byte_offset = content_start;
         loop {
+            if let Some(chunk_size) = self.string_chunk_size {
+                if next_byte_offset - content_start >= chunk_size
+                    && input_buffer.get(next_byte_offset) != Some(&b'"')
+                    && is_utf8_char_boundary(&input_buffer[content_start..next_byte_offset])
+                {
+                    let result = self.string_content(
+                        input_buffer,
+                        content_start..next_byte_offset,
+                        string,
+                        error,
+                    );
+                    self.file_offset += u64::try_from(next_byte_offset).unwrap();
+                    self.is_in_string = true;
+                    return Some(result.map(JsonToken::StringChunk));
+                }
+            }
             match *input_buffer.get(next_byte_offset)? {
                 b'"' => {
                     // end of string
-                    let result = Some(if let Some(error) = error {
-                        Err(error)
-                    } else if let Some((mut string, read_until)) = string {
-                        if read_until < next_byte_offset {
-                            let (str, e) = self.decode_utf8(
-                                &input_buffer[read_until..next_byte_offset],
-                                self.file_offset + u64::try_from(read_until).unwrap(),
-                            );
-                            error = error.or(e);
-                            string.push_str(&str);
-                        }
-                        if let Some(error) = error {
-                            Err(error)
-                        } else {
-                            Ok(JsonToken::String(Cow::Owned(string)))
-                        }
-                    } else {
-                        let (string, error) = self
-                            .decode_utf8(&input_buffer[1..next_byte_offset], self.file_offset + 1);
-                        if let Some(error) = error {
-                            Err(error)
-                        } else {
-                            Ok(JsonToken::String(string))
-                        }
-                    });
+                    let result = self.string_content(
+                        input_buffer,
+                        content_start..next_byte_offset,
+                        string,
+                        error,
+                    );
                     self.file_offset += u64::try_from(next_byte_offset).unwrap() + 1;
-                    return result;
+                    self.is_in_string = false;
+                    return Some(result.map(JsonToken::String));
                 }
                 b'\\' => {
                     // Escape sequences
                     if string.is_none() {
-                        string = Some((String::new(), 1))
+                        string = Some((String::new(), content_start))
                     }
                     let (string, read_until) = string.as_mut().unwrap();
                     if *read_until < next_byte_offset {
@@ -849,6 +3067,54 @@ impl JsonLexer {
                             };
                             if let Some(c) = char::from_u32(code_point) {
                                 string.push(c);
+                            } else if self.lone_surrogates.is_some()
+                                || self.decoding_warnings.is_some()
+                            {
+                                // We look for a low surrogate following a high surrogate
+                                let mut low_surrogate = None;
+                                if (0xD800..=0xDBFF).contains(&code_point)
+                                    && *input_buffer.get(next_byte_offset)? == b'\\'
+                                    && *input_buffer.get(next_byte_offset + 1)? == b'u'
+                                {
+                                    let val = input_buffer
+                                        .get(next_byte_offset + 2..next_byte_offset + 6)?;
+                                    low_surrogate = read_hexa_char(val)
+                                        .ok()
+                                        .filter(|cp| (0xDC00..=0xDFFF).contains(cp));
+                                }
+                                if let Some(low_surrogate) = low_surrogate {
+                                    next_byte_offset += 6;
+                                    string.push(
+                                        char::from_u32(
+                                            0x10000
+                                                + ((code_point & 0x03FF) << 10)
+                                                + (low_surrogate & 0x03FF),
+                                        )
+                                        .unwrap_or(char::REPLACEMENT_CHARACTER),
+                                    );
+                                } else {
+                                    if let Some(lone_surrogates) = &mut self.lone_surrogates {
+                                        lone_surrogates.push(LoneSurrogate {
+                                            offset: string.len(),
+                                            code_unit: u16::try_from(code_point).unwrap(),
+                                            hex_digits: val.try_into().unwrap(),
+                                        });
+                                    } else {
+                                        let pos = self.file_offset
+                                            + u64::try_from(next_byte_offset).unwrap();
+                                        let warning = self.syntax_error(
+                                            pos - 6..pos,
+                                            format!(
+                                                "\\u{code_point:X} is a lone surrogate, it has been replaced by U+FFFD"
+                                            ),
+                                        );
+                                        if let Some(decoding_warnings) = &mut self.decoding_warnings
+                                        {
+                                            decoding_warnings.push(warning);
+                                        }
+                                    }
+                                    string.push(char::REPLACEMENT_CHARACTER);
+                                }
                             } else {
                                 let high_surrogate = code_point;
                                 if !(0xD800..=0xDBFF).contains(&high_surrogate) {
@@ -956,6 +3222,42 @@ impl JsonLexer {
         }
     }
 
+    /// Builds the content of a string from the bytes in `range` and the already decoded prefix `string` if any.
+    fn string_content<'a>(
+        &mut self,
+        input_buffer: &'a [u8],
+        range: Range<usize>,
+        string: Option<(String, usize)>,
+        mut error: Option<JsonSyntaxError>,
+    ) -> Result<Cow<'a, str>, JsonSyntaxError> {
+        if let Some(error) = error {
+            return Err(error);
+        }
+        let content = if let Some((mut string, read_until)) = string {
+            if read_until < range.end {
+                let (str, e) = self.decode_utf8(
+                    &input_buffer[read_until..range.end],
+                    self.file_offset + u64::try_from(read_until).unwrap(),
+                );
+                error = e;
+                string.push_str(&str);
+            }
+            Cow::Owned(string)
+        } else {
+            let (string, e) = self.decode_utf8(
+                &input_buffer[range.clone()],
+                self.file_offset + u64::try_from(range.start).unwrap(),
+            );
+            error = e;
+            string
+        };
+        if let Some(error) = error {
+            Err(error)
+        } else {
+            Ok(content)
+        }
+    }
+
     #[inline]
     fn read_constant(
         &mut self,
@@ -1082,23 +3384,63 @@ impl JsonLexer {
     }
 
     #[inline]
+    /// Decodes UTF-8 bytes located at `start_position` in the file.
+    ///
+    /// In lossy mode, each invalid sequence is replaced by U+FFFD and a warning is recorded.
     fn decode_utf8<'a>(
-        &self,
+        &mut self,
         input_buffer: &'a [u8],
         start_position: u64,
     ) -> (Cow<'a, str>, Option<JsonSyntaxError>) {
         match str::from_utf8(input_buffer) {
             Ok(str) => (Cow::Borrowed(str), None),
-            Err(e) => (
-                String::from_utf8_lossy(input_buffer),
-                Some({
-                    let pos = start_position + u64::try_from(e.valid_up_to()).unwrap();
-                    self.syntax_error(pos..pos + 1, format!("Invalid UTF-8: {e}"))
-                }),
-            ),
+            Err(e) => {
+                let Some(mut decoding_warnings) = self.decoding_warnings.take() else {
+                    return (
+                        String::from_utf8_lossy(input_buffer),
+                        Some({
+                            let pos = start_position + u64::try_from(e.valid_up_to()).unwrap();
+                            self.syntax_error(pos..pos + 1, format!("Invalid UTF-8: {e}"))
+                        }),
+                    );
+                };
+                let mut position = 0;
+                let mut error = e;
+                loop {
+                    let start = position + error.valid_up_to();
+                    let end = error
+                        .error_len()
+                        .map_or(input_buffer.len(), |error_len| start + error_len);
+                    decoding_warnings.push(self.syntax_error(
+                        start_position + u64::try_from(start).unwrap()
+                            ..start_position + u64::try_from(end).unwrap(),
+                        format!(
+                            "Invalid UTF-8 sequence {:X?}, it has been replaced by U+FFFD",
+                            &input_buffer[start..end]
+                        ),
+                    ));
+                    position = end;
+                    match str::from_utf8(&input_buffer[position..]) {
+                        Ok(_) => break,
+                        Err(e) => error = e,
+                    }
+                }
+                self.decoding_warnings = Some(decoding_warnings);
+                (String::from_utf8_lossy(input_buffer), None)
+            }
         }
     }
 
+    /// Builds an error for an exceeded limit located on the last token
+    fn limit_error(&self, limit: JsonLimit, message: String) -> JsonSyntaxError {
+        let mut error = self.syntax_error(
+            self.file_start_of_last_token..max(self.file_offset, self.file_start_of_last_token + 1),
+            message,
+        );
+        error.exceeded_limit = Some(limit);
+        error
+    }
+
     fn syntax_error(&self, file_offset: Range<u64>, message: impl Into<String>) -> JsonSyntaxError {
         let start_file_offset = max(file_offset.start, self.file_start_of_last_line);
         JsonSyntaxError {
@@ -1112,8 +3454,28 @@ impl JsonLexer {
                 offset: file_offset.end,
             },
             message: message.into(),
+            exceeded_limit: None,
+        }
+    }
+}
+
+/// Checks that `input` does not end in the middle of a UTF-8 encoded character.
+///
+/// Invalid sequences are considered to end at a boundary, they are reported by the decoding.
+fn is_utf8_char_boundary(input: &[u8]) -> bool {
+    for (i, b) in input.iter().rev().take(4).enumerate() {
+        if b & 0xC0 != 0x80 {
+            // Not a continuation byte
+            let len = match b {
+                0xC0..=0xDF => 2,
+                0xE0..=0xEF => 3,
+                0xF0..=0xF7 => 4,
+                _ => 1,
+            };
+            return len <= i + 1;
         }
     }
+    true
 }
 
 #[inline]
@@ -1148,20 +3510,41 @@ fn read_digits(input_buffer: &[u8], is_ending: bool) -> Option<usize> {
     Some(count)
 }
 
-#[inline]
-fn owned_event(event: JsonEvent<'_>) -> JsonEvent<'static> {
-    match event {
-        JsonEvent::String(s) => JsonEvent::String(s.into_owned().into()),
-        JsonEvent::Number(n) => JsonEvent::Number(n.into_owned().into()),
-        JsonEvent::Boolean(b) => JsonEvent::Boolean(b),
-        JsonEvent::Null => JsonEvent::Null,
-        JsonEvent::StartArray => JsonEvent::StartArray,
-        JsonEvent::EndArray => JsonEvent::EndArray,
-        JsonEvent::StartObject => JsonEvent::StartObject,
-        JsonEvent::EndObject => JsonEvent::EndObject,
-        JsonEvent::ObjectKey(k) => JsonEvent::ObjectKey(k.into_owned().into()),
-        JsonEvent::Eof => JsonEvent::Eof,
+pub(crate) fn max_buffer_size_error(max_buffer_size: usize) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::OutOfMemory,
+        format!("Reached the buffer maximal size of {max_buffer_size}"),
+    )
+}
+
+/// Maps the positions in the output of `input_decoder` to positions in its input
+///
+/// The decoding warnings are only mapped if they have been set by the last call to [`LowLevelJsonParser::parse_next`]
+/// i.e. if `reads_tokens` was true before it.
+fn map_to_source_positions<'a>(
+    input_decoder: &mut InputDecoder,
+    parser: &mut LowLevelJsonParser,
+    event: Result<JsonEvent<'a>, JsonSyntaxError>,
+    reads_tokens: bool,
+) -> Result<JsonEvent<'a>, JsonSyntaxError> {
+    let event = event.map_err(|mut error| {
+        error.map_offsets(|offset| input_decoder.source_offset(offset));
+        error
+    });
+    if reads_tokens {
+        for warning in parser.lexer.decoding_warnings.iter_mut().flatten() {
+            warning.map_offsets(|offset| input_decoder.source_offset(offset));
+        }
+    }
+    for diagnostic in parser.new_diagnostics_mut() {
+        diagnostic.map_offsets(|offset| input_decoder.source_offset(offset));
     }
+    // Later positions are never before the current line and token
+    input_decoder.forget_before(min(
+        parser.lexer.file_start_of_last_line,
+        parser.lexer.file_start_of_last_token,
+    ));
+    event
 }
 
 /// Result of [`LowLevelJsonParser::parse_next`].
@@ -1188,6 +3571,7 @@ pub struct TextPosition {
 pub struct JsonSyntaxError {
     location: Range<TextPosition>,
     message: String,
+    exceeded_limit: Option<JsonLimit>,
 }
 
 impl JsonSyntaxError {
@@ -1202,6 +3586,147 @@ impl JsonSyntaxError {
     pub fn message(&self) -> &str {
         &self.message
     }
+
+    /// The limit set with [`JsonLimits`] that has been exceeded, if the error is caused by one.
+    #[inline]
+    pub fn exceeded_limit(&self) -> Option<JsonLimit> {
+        self.exceeded_limit
+    }
+
+    /// Changes the offsets of the location, the columns are computed again from the start of the lines.
+    pub(crate) fn map_offsets(&mut self, map: impl Fn(u64) -> u64) {
+        for position in [&mut self.location.start, &mut self.location.end] {
+            let line_start = map(position.offset - position.column);
+            position.offset = map(position.offset);
+            position.column = position.offset.saturating_sub(line_start);
+        }
+    }
+}
+
+/// An escaped lone UTF-16 surrogate like `\uD888` replaced by U+FFFD in a string.
+///
+/// See [`LowLevelJsonParser::with_lone_surrogate_preservation`].
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Hash)]
+pub struct LoneSurrogate {
+    /// The position in bytes of the U+FFFD replacement character in the string.
+    pub offset: usize,
+    /// The surrogate UTF-16 code unit, between 0xD800 and 0xDFFF.
+    pub code_unit: u16,
+    /// The four hexadecimal digits of the escape sequence as written in the input, like `*b"D888"`.
+    ///
+    /// They keep the case of the input so that the escape sequence is written back unchanged.
+    pub hex_digits: [u8; 4],
+}
+
+impl LoneSurrogate {
+    /// Checks that [`hex_digits`](Self::hex_digits) encode [`code_unit`](Self::code_unit)
+    pub(crate) fn has_matching_hex_digits(&self) -> bool {
+        read_hexa_char(&self.hex_digits) == Ok(self.code_unit.into())
+    }
+}
+
+/// Limits on the parsed input to protect against malicious documents.
+///
+/// They are set on a parser with [`LowLevelJsonParser::with_limits`] or the equivalent methods of the other parsers.
+/// All limits are disabled by default.
+///
+/// ```
+/// use json_event_parser::{JsonLimits, ReaderJsonParser};
+///
+/// let parser = ReaderJsonParser::new(b"[]".as_slice()).with_limits(
+///     JsonLimits::new()
+///         .with_max_document_size(1024 * 1024)
+///         .with_max_string_length(1024)
+///         .with_max_event_count(10_000),
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+#[must_use]
+pub struct JsonLimits {
+    max_string_length: Option<usize>,
+    max_number_length: Option<usize>,
+    max_document_size: Option<u64>,
+    max_object_members: Option<usize>,
+    max_array_elements: Option<usize>,
+    max_event_count: Option<u64>,
+}
+
+impl JsonLimits {
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            max_string_length: None,
+            max_number_length: None,
+            max_document_size: None,
+            max_object_members: None,
+            max_array_elements: None,
+            max_event_count: None,
+        }
+    }
+
+    /// Maximal length in bytes of strings and object keys, as written in the input.
+    ///
+    /// Escape sequences are counted as they are written and not after decoding.
+    /// The limit applies to the full string even if it is returned in chunks.
+    #[inline]
+    pub fn with_max_string_length(mut self, length: usize) -> Self {
+        self.max_string_length = Some(length);
+        self
+    }
+
+    /// Maximal length in bytes of number literals.
+    #[inline]
+    pub fn with_max_number_length(mut self, length: usize) -> Self {
+        self.max_number_length = Some(length);
+        self
+    }
+
+    /// Maximal size in bytes of the document, byte order mark and whitespaces included.
+    #[inline]
+    pub fn with_max_document_size(mut self, size: u64) -> Self {
+        self.max_document_size = Some(size);
+        self
+    }
+
+    /// Maximal number of members of each object.
+    #[inline]
+    pub fn with_max_object_members(mut self, count: usize) -> Self {
+        self.max_object_members = Some(count);
+        self
+    }
+
+    /// Maximal number of elements of each array.
+    #[inline]
+    pub fn with_max_array_elements(mut self, count: usize) -> Self {
+        self.max_array_elements = Some(count);
+        self
+    }
+
+    /// Maximal number of events in the document, [`JsonEvent::Eof`] excluded.
+    ///
+    /// Each chunk of a string returned in chunks is counted as an event.
+    #[inline]
+    pub fn with_max_event_count(mut self, count: u64) -> Self {
+        self.max_event_count = Some(count);
+        self
+    }
+}
+
+/// A limit from [`JsonLimits`].
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Hash)]
+pub enum JsonLimit {
+    /// See [`JsonLimits::with_max_string_length`].
+    StringLength,
+    /// See [`JsonLimits::with_max_number_length`].
+    NumberLength,
+    /// See [`JsonLimits::with_max_document_size`].
+    DocumentSize,
+    /// See [`JsonLimits::with_max_object_members`].
+    ObjectMembers,
+    /// See [`JsonLimits::with_max_array_elements`].
+    ArrayElements,
+    /// See [`JsonLimits::with_max_event_count`].
+    EventCount,
 }
 
 impl fmt::Display for JsonSyntaxError {
@@ -1292,6 +3817,13 @@ impl From<io::Error> for JsonParseError {
     }
 }
 
+impl From<Infallible> for JsonParseError {
+    #[inline]
+    fn from(error: Infallible) -> Self {
+        match error {}
+    }
+}
+
 impl From<JsonParseError> for io::Error {
     #[inline]
     fn from(error: JsonParseError) -> Self {
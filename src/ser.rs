@@ -0,0 +1,678 @@
+#[cfg(feature = "async-tokio")]
+use crate::TokioAsyncWriterJsonSerializer;
+use crate::{JsonEvent, LowLevelJsonSerializer, WriterJsonSerializer};
+use serde::ser::{
+    self, Impossible, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant,
+    SerializeTuple, SerializeTupleStruct, SerializeTupleVariant,
+};
+use serde::Serialize;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+#[cfg(feature = "async-tokio")]
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+impl<W: Write> WriterJsonSerializer<W> {
+    /// Serializes a value using [serde](https://serde.rs/).
+    ///
+    /// It can be mixed with [`serialize_event`](Self::serialize_event) to write large documents without buffering them.
+    /// If an error is returned, a part of the value might have already been written.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, WriterJsonSerializer};
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Serialize)]
+    /// struct Point {
+    ///     x: i64,
+    ///     y: i64,
+    /// }
+    ///
+    /// let mut writer = WriterJsonSerializer::new(Vec::new());
+    /// writer.serialize_event(JsonEvent::StartArray)?;
+    /// for i in 0..2 {
+    ///     writer.serialize_value(&Point { x: i, y: i + 1 })?;
+    /// }
+    /// writer.serialize_event(JsonEvent::EndArray)?;
+    /// assert_eq!(
+    ///     writer.finish()?.as_slice(),
+    ///     b"[{\"x\":0,\"y\":1},{\"x\":1,\"y\":2}]"
+    /// );
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> io::Result<()> {
+        self.writer.serialize_value(value, &mut self.write)
+    }
+}
+
+#[cfg(feature = "async-tokio")]
+impl<W: AsyncWrite + Unpin> TokioAsyncWriterJsonSerializer<W> {
+    /// Serializes a value using [serde](https://serde.rs/).
+    ///
+    /// It can be mixed with [`serialize_event`](Self::serialize_event) to write large documents without buffering them.
+    /// Only the serialization of `value` is buffered before being written.
+    /// If an error is returned, the part of the value serialized before the error is written,
+    /// like with [`WriterJsonSerializer::serialize_value`](crate::WriterJsonSerializer::serialize_value).
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, TokioAsyncWriterJsonSerializer};
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> ::std::io::Result<()> {
+    /// let mut writer = TokioAsyncWriterJsonSerializer::new(Vec::new());
+    /// writer.serialize_event(JsonEvent::StartArray).await?;
+    /// writer.serialize_value(&(1, "foo")).await?;
+    /// writer.serialize_event(JsonEvent::EndArray).await?;
+    /// assert_eq!(writer.finish()?.as_slice(), b"[[1,\"foo\"]]");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> io::Result<()> {
+        let result = self.writer.serialize_value(value, &mut self.buffer);
+        // The part serialized before an error is written too to keep the output in sync with the serializer state
+        let write_result = self.write.write_all(&self.buffer).await;
+        self.buffer.clear();
+        result?;
+        write_result
+    }
+}
+
+impl LowLevelJsonSerializer {
+    /// Serializes a value using [serde](https://serde.rs/).
+    ///
+    /// It can be mixed with [`serialize_event`](Self::serialize_event) to write large documents without buffering them.
+    /// If an error is returned, a part of the value might have already been written.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, LowLevelJsonSerializer};
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut writer = LowLevelJsonSerializer::new();
+    /// let mut output = Vec::new();
+    /// writer.serialize_event(JsonEvent::StartObject, &mut output)?;
+    /// writer.serialize_event(JsonEvent::ObjectKey("foo".into()), &mut output)?;
+    /// writer.serialize_value(&BTreeMap::from([(1, Some(true)), (2, None)]), &mut output)?;
+    /// writer.serialize_event(JsonEvent::EndObject, &mut output)?;
+    /// assert_eq!(output.as_slice(), b"{\"foo\":{\"1\":true,\"2\":null}}");
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn serialize_value<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+        write: impl Write,
+    ) -> io::Result<()> {
+        value
+            .serialize(&mut EventSerializer {
+                serializer: self,
+                write,
+            })
+            .map_err(|e| e.0)
+    }
+}
+
+/// Error raised by [`EventSerializer`].
+#[derive(Debug)]
+struct SerializeError(io::Error);
+
+impl fmt::Display for SerializeError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Error for SerializeError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+impl ser::Error for SerializeError {
+    #[inline]
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(io::Error::new(io::ErrorKind::InvalidInput, msg.to_string()))
+    }
+}
+
+impl From<io::Error> for SerializeError {
+    #[inline]
+    fn from(error: io::Error) -> Self {
+        Self(error)
+    }
+}
+
+/// A serde serializer converting values to [`JsonEvent`] and writing them with a [`LowLevelJsonSerializer`].
+struct EventSerializer<'a, W: Write> {
+    serializer: &'a mut LowLevelJsonSerializer,
+    write: W,
+}
+
+impl<W: Write> EventSerializer<'_, W> {
+    fn emit(&mut self, event: JsonEvent<'_>) -> Result<(), SerializeError> {
+        Ok(self.serializer.serialize_event(event, &mut self.write)?)
+    }
+
+    fn start_variant(&mut self, variant: &'static str) -> Result<(), SerializeError> {
+        self.emit(JsonEvent::StartObject)?;
+        self.emit(JsonEvent::ObjectKey(variant.into()))
+    }
+}
+
+impl<'a, 'b, W: Write> ser::Serializer for &'a mut EventSerializer<'b, W> {
+    type Ok = ();
+    type Error = SerializeError;
+    type SerializeSeq = Compound<'a, 'b, W>;
+    type SerializeTuple = Compound<'a, 'b, W>;
+    type SerializeTupleStruct = Compound<'a, 'b, W>;
+    type SerializeTupleVariant = Compound<'a, 'b, W>;
+    type SerializeMap = Compound<'a, 'b, W>;
+    type SerializeStruct = Compound<'a, 'b, W>;
+    type SerializeStructVariant = Compound<'a, 'b, W>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), SerializeError> {
+        self.emit(v.into())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), SerializeError> {
+        self.emit(v.into())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), SerializeError> {
+        self.emit(v.into())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), SerializeError> {
+        self.emit(v.into())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), SerializeError> {
+        self.emit(v.into())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), SerializeError> {
+        self.emit(v.into())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), SerializeError> {
+        self.emit(v.into())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), SerializeError> {
+        self.emit(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), SerializeError> {
+        self.emit(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), SerializeError> {
+        self.emit(v.into())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), SerializeError> {
+        self.emit(v.into())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), SerializeError> {
+        self.emit(v.into())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), SerializeError> {
+        self.emit(v.into())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), SerializeError> {
+        self.emit(v.into())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), SerializeError> {
+        self.emit(v.into())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), SerializeError> {
+        self.emit(JsonEvent::StartArray)?;
+        for b in v {
+            self.emit((*b).into())?;
+        }
+        self.emit(JsonEvent::EndArray)
+    }
+
+    fn serialize_none(self) -> Result<(), SerializeError> {
+        self.emit(JsonEvent::Null)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), SerializeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), SerializeError> {
+        self.emit(JsonEvent::Null)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), SerializeError> {
+        self.emit(JsonEvent::Null)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), SerializeError> {
+        self.emit(variant.into())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        self.start_variant(variant)?;
+        value.serialize(&mut *self)?;
+        self.emit(JsonEvent::EndObject)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Compound<'a, 'b, W>, SerializeError> {
+        self.emit(JsonEvent::StartArray)?;
+        Ok(Compound {
+            serializer: self,
+            is_variant: false,
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Compound<'a, 'b, W>, SerializeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Compound<'a, 'b, W>, SerializeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a, 'b, W>, SerializeError> {
+        self.start_variant(variant)?;
+        self.emit(JsonEvent::StartArray)?;
+        Ok(Compound {
+            serializer: self,
+            is_variant: true,
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Compound<'a, 'b, W>, SerializeError> {
+        self.emit(JsonEvent::StartObject)?;
+        Ok(Compound {
+            serializer: self,
+            is_variant: false,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Compound<'a, 'b, W>, SerializeError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a, 'b, W>, SerializeError> {
+        self.start_variant(variant)?;
+        self.emit(JsonEvent::StartObject)?;
+        Ok(Compound {
+            serializer: self,
+            is_variant: true,
+        })
+    }
+}
+
+/// Serialization of arrays and objects.
+///
+/// If `is_variant` is set, the array or object is the value of an enum variant wrapped in an object.
+struct Compound<'a, 'b, W: Write> {
+    serializer: &'a mut EventSerializer<'b, W>,
+    is_variant: bool,
+}
+
+impl<W: Write> Compound<'_, '_, W> {
+    fn end_with(self, event: JsonEvent<'static>) -> Result<(), SerializeError> {
+        self.serializer.emit(event)?;
+        if self.is_variant {
+            self.serializer.emit(JsonEvent::EndObject)?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> SerializeSeq for Compound<'_, '_, W> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        value.serialize(&mut *self.serializer)
+    }
+
+    fn end(self) -> Result<(), SerializeError> {
+        self.end_with(JsonEvent::EndArray)
+    }
+}
+
+impl<W: Write> SerializeTuple for Compound<'_, '_, W> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        value.serialize(&mut *self.serializer)
+    }
+
+    fn end(self) -> Result<(), SerializeError> {
+        self.end_with(JsonEvent::EndArray)
+    }
+}
+
+impl<W: Write> SerializeTupleStruct for Compound<'_, '_, W> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        value.serialize(&mut *self.serializer)
+    }
+
+    fn end(self) -> Result<(), SerializeError> {
+        self.end_with(JsonEvent::EndArray)
+    }
+}
+
+impl<W: Write> SerializeTupleVariant for Compound<'_, '_, W> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        value.serialize(&mut *self.serializer)
+    }
+
+    fn end(self) -> Result<(), SerializeError> {
+        self.end_with(JsonEvent::EndArray)
+    }
+}
+
+impl<W: Write> SerializeMap for Compound<'_, '_, W> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), SerializeError> {
+        key.serialize(KeySerializer {
+            serializer: &mut *self.serializer,
+        })
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        value.serialize(&mut *self.serializer)
+    }
+
+    fn end(self) -> Result<(), SerializeError> {
+        self.end_with(JsonEvent::EndObject)
+    }
+}
+
+impl<W: Write> SerializeStruct for Compound<'_, '_, W> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        self.serializer.emit(JsonEvent::ObjectKey(key.into()))?;
+        value.serialize(&mut *self.serializer)
+    }
+
+    fn end(self) -> Result<(), SerializeError> {
+        self.end_with(JsonEvent::EndObject)
+    }
+}
+
+impl<W: Write> SerializeStructVariant for Compound<'_, '_, W> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        self.serializer.emit(JsonEvent::ObjectKey(key.into()))?;
+        value.serialize(&mut *self.serializer)
+    }
+
+    fn end(self) -> Result<(), SerializeError> {
+        self.end_with(JsonEvent::EndObject)
+    }
+}
+
+/// Serializes object keys.
+///
+/// JSON keys are always strings so only strings, chars, integers and unit variants are allowed.
+struct KeySerializer<'a, 'b, W: Write> {
+    serializer: &'a mut EventSerializer<'b, W>,
+}
+
+impl<W: Write> KeySerializer<'_, '_, W> {
+    fn emit_key(self, key: &str) -> Result<(), SerializeError> {
+        self.serializer.emit(JsonEvent::ObjectKey(key.into()))
+    }
+}
+
+fn key_must_be_a_string() -> SerializeError {
+    SerializeError(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "JSON object keys must be strings",
+    ))
+}
+
+impl<W: Write> ser::Serializer for KeySerializer<'_, '_, W> {
+    type Ok = ();
+    type Error = SerializeError;
+    type SerializeSeq = Impossible<(), SerializeError>;
+    type SerializeTuple = Impossible<(), SerializeError>;
+    type SerializeTupleStruct = Impossible<(), SerializeError>;
+    type SerializeTupleVariant = Impossible<(), SerializeError>;
+    type SerializeMap = Impossible<(), SerializeError>;
+    type SerializeStruct = Impossible<(), SerializeError>;
+    type SerializeStructVariant = Impossible<(), SerializeError>;
+
+    fn serialize_bool(self, _v: bool) -> Result<(), SerializeError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), SerializeError> {
+        self.emit_key(&v.to_string())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), SerializeError> {
+        self.emit_key(&v.to_string())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), SerializeError> {
+        self.emit_key(&v.to_string())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), SerializeError> {
+        self.emit_key(&v.to_string())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), SerializeError> {
+        self.emit_key(&v.to_string())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), SerializeError> {
+        self.emit_key(&v.to_string())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), SerializeError> {
+        self.emit_key(&v.to_string())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), SerializeError> {
+        self.emit_key(&v.to_string())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), SerializeError> {
+        self.emit_key(&v.to_string())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), SerializeError> {
+        self.emit_key(&v.to_string())
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<(), SerializeError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<(), SerializeError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), SerializeError> {
+        self.emit_key(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), SerializeError> {
+        self.emit_key(v)
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<(), SerializeError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_none(self) -> Result<(), SerializeError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<(), SerializeError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_unit(self) -> Result<(), SerializeError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), SerializeError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), SerializeError> {
+        self.emit_key(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<(), SerializeError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_seq(
+        self,
+        _len: Option<usize>,
+    ) -> Result<Impossible<(), SerializeError>, SerializeError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_tuple(
+        self,
+        _len: usize,
+    ) -> Result<Impossible<(), SerializeError>, SerializeError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Impossible<(), SerializeError>, SerializeError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Impossible<(), SerializeError>, SerializeError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_map(
+        self,
+        _len: Option<usize>,
+    ) -> Result<Impossible<(), SerializeError>, SerializeError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Impossible<(), SerializeError>, SerializeError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Impossible<(), SerializeError>, SerializeError> {
+        Err(key_must_be_a_string())
+    }
+}
@@ -0,0 +1,176 @@
+#[cfg(feature = "async-tokio")]
+use crate::TokioAsyncWriterJsonSerializer;
+use crate::{JsonEvent, LowLevelJsonSerializer, WriterJsonSerializer};
+use std::convert::Infallible;
+#[cfg(feature = "async-tokio")]
+use std::future::{ready, Future};
+use std::io::{self, Write};
+#[cfg(feature = "async-tokio")]
+use tokio::io::AsyncWrite;
+
+/// A destination of JSON events.
+///
+/// It allows to write code emitting events once and to use it with any destination.
+/// It is implemented by [`WriterJsonSerializer`], by [`LowLevelJsonSerializerSink`] that pairs a [`LowLevelJsonSerializer`] with its output
+/// and by [`Vec<JsonEvent<'static>>`] that collects the events.
+///
+/// ```
+/// use json_event_parser::{JsonEvent, JsonEventSink, WriterJsonSerializer};
+///
+/// fn emit_point<S: JsonEventSink>(sink: &mut S, x: i64, y: i64) -> Result<(), S::Error> {
+///     sink.serialize_event(JsonEvent::StartArray)?;
+///     sink.serialize_event(x.into())?;
+///     sink.serialize_event(y.into())?;
+///     sink.serialize_event(JsonEvent::EndArray)
+/// }
+///
+/// let mut serializer = WriterJsonSerializer::new(Vec::new());
+/// emit_point(&mut serializer, 1, 2)?;
+/// assert_eq!(serializer.finish()?.as_slice(), b"[1,2]");
+///
+/// let mut events = Vec::new();
+/// emit_point(&mut events, 1, 2).unwrap();
+/// assert_eq!(events.len(), 4);
+/// # std::io::Result::Ok(())
+/// ```
+pub trait JsonEventSink {
+    /// The error returned if an event is not accepted.
+    type Error;
+
+    /// Consumes a new event.
+    fn serialize_event(&mut self, event: JsonEvent<'_>) -> Result<(), Self::Error>;
+}
+
+impl<W: Write> JsonEventSink for WriterJsonSerializer<W> {
+    type Error = io::Error;
+
+    #[inline]
+    fn serialize_event(&mut self, event: JsonEvent<'_>) -> io::Result<()> {
+        WriterJsonSerializer::serialize_event(self, event)
+    }
+}
+
+impl<W: Write> JsonEventSink for LowLevelJsonSerializerSink<'_, W> {
+    type Error = io::Error;
+
+    #[inline]
+    fn serialize_event(&mut self, event: JsonEvent<'_>) -> io::Result<()> {
+        self.serializer.serialize_event(event, &mut self.write)
+    }
+}
+
+impl JsonEventSink for Vec<JsonEvent<'static>> {
+    type Error = Infallible;
+
+    #[inline]
+    fn serialize_event(&mut self, event: JsonEvent<'_>) -> Result<(), Infallible> {
+        self.push(event.into_owned());
+        Ok(())
+    }
+}
+
+impl<S: JsonEventSink + ?Sized> JsonEventSink for &mut S {
+    type Error = S::Error;
+
+    #[inline]
+    fn serialize_event(&mut self, event: JsonEvent<'_>) -> Result<(), S::Error> {
+        (**self).serialize_event(event)
+    }
+}
+
+/// A [`LowLevelJsonSerializer`] paired with the [`Write`] implementation it writes to, usable as a [`JsonEventSink`].
+///
+/// It is built with [`LowLevelJsonSerializer::sink`].
+///
+/// ```
+/// use json_event_parser::{JsonEvent, JsonEventSink, LowLevelJsonSerializer};
+///
+/// let mut serializer = LowLevelJsonSerializer::new();
+/// let mut output = Vec::new();
+/// let mut sink = serializer.sink(&mut output);
+/// sink.serialize_event(JsonEvent::StartArray)?;
+/// sink.serialize_event(JsonEvent::Null)?;
+/// sink.serialize_event(JsonEvent::EndArray)?;
+/// assert_eq!(output.as_slice(), b"[null]");
+/// # std::io::Result::Ok(())
+/// ```
+pub struct LowLevelJsonSerializerSink<'a, W: Write> {
+    pub(crate) serializer: &'a mut LowLevelJsonSerializer,
+    pub(crate) write: W,
+}
+
+/// An asynchronous destination of JSON events.
+///
+/// It is the asynchronous counterpart of [`JsonEventSink`].
+/// It is implemented by [`TokioAsyncWriterJsonSerializer`] and by [`Vec<JsonEvent<'static>>`] that collects the events.
+///
+/// ```
+/// use json_event_parser::{JsonEvent, TokioAsyncJsonEventSink, TokioAsyncWriterJsonSerializer};
+///
+/// async fn emit_point<S: TokioAsyncJsonEventSink>(
+///     sink: &mut S,
+///     x: i64,
+///     y: i64,
+/// ) -> Result<(), S::Error> {
+///     sink.serialize_event(JsonEvent::StartArray).await?;
+///     sink.serialize_event(x.into()).await?;
+///     sink.serialize_event(y.into()).await?;
+///     sink.serialize_event(JsonEvent::EndArray).await
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> ::std::io::Result<()> {
+/// let mut serializer = TokioAsyncWriterJsonSerializer::new(Vec::new());
+/// emit_point(&mut serializer, 1, 2).await?;
+/// assert_eq!(serializer.finish()?.as_slice(), b"[1,2]");
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "async-tokio")]
+pub trait TokioAsyncJsonEventSink {
+    /// The error returned if an event is not accepted.
+    type Error;
+
+    /// Consumes a new event.
+    fn serialize_event(
+        &mut self,
+        event: JsonEvent<'_>,
+    ) -> impl Future<Output = Result<(), Self::Error>>;
+}
+
+#[cfg(feature = "async-tokio")]
+impl<W: AsyncWrite + Unpin> TokioAsyncJsonEventSink for TokioAsyncWriterJsonSerializer<W> {
+    type Error = io::Error;
+
+    #[inline]
+    fn serialize_event(&mut self, event: JsonEvent<'_>) -> impl Future<Output = io::Result<()>> {
+        TokioAsyncWriterJsonSerializer::serialize_event(self, event)
+    }
+}
+
+#[cfg(feature = "async-tokio")]
+impl TokioAsyncJsonEventSink for Vec<JsonEvent<'static>> {
+    type Error = Infallible;
+
+    #[inline]
+    fn serialize_event(
+        &mut self,
+        event: JsonEvent<'_>,
+    ) -> impl Future<Output = Result<(), Infallible>> {
+        self.push(event.into_owned());
+        ready(Ok(()))
+    }
+}
+
+#[cfg(feature = "async-tokio")]
+impl<S: TokioAsyncJsonEventSink + ?Sized> TokioAsyncJsonEventSink for &mut S {
+    type Error = S::Error;
+
+    #[inline]
+    fn serialize_event(
+        &mut self,
+        event: JsonEvent<'_>,
+    ) -> impl Future<Output = Result<(), S::Error>> {
+        (**self).serialize_event(event)
+    }
+}
@@ -0,0 +1,206 @@
+use crate::{JsonEvent, TextPosition};
+use std::io;
+
+/// Start of all snapshots: a magic number followed by the format version
+const SNAPSHOT_HEADER: &[u8] = b"JEPS\x01";
+
+/// Writes the parser state in the compact snapshot format.
+///
+/// Integers are written as unsigned LEB128 and strings are prefixed by their length.
+pub(crate) struct SnapshotWriter {
+    buffer: Vec<u8>,
+}
+
+impl SnapshotWriter {
+    pub(crate) fn new() -> Self {
+        Self {
+            buffer: SNAPSHOT_HEADER.to_vec(),
+        }
+    }
+
+    pub(crate) fn write_u64(&mut self, mut value: u64) {
+        loop {
+            let byte = (value & 0x7F) as u8;
+            value >>= 7;
+            if value == 0 {
+                self.buffer.push(byte);
+                return;
+            }
+            self.buffer.push(byte | 0x80);
+        }
+    }
+
+    pub(crate) fn write_usize(&mut self, value: usize) {
+        self.write_u64(value.try_into().unwrap());
+    }
+
+    pub(crate) fn write_u8(&mut self, value: u8) {
+        self.buffer.push(value);
+    }
+
+    pub(crate) fn write_bool(&mut self, value: bool) {
+        self.write_u8(value.into());
+    }
+
+    pub(crate) fn write_str(&mut self, value: &str) {
+        self.write_usize(value.len());
+        self.buffer.extend_from_slice(value.as_bytes());
+    }
+
+    pub(crate) fn write_position(&mut self, position: TextPosition) {
+        self.write_u64(position.line);
+        self.write_u64(position.column);
+        self.write_u64(position.offset);
+    }
+
+    pub(crate) fn write_event(&mut self, event: &JsonEvent<'_>) {
+        match event {
+            JsonEvent::String(value) => {
+                self.write_u8(0);
+                self.write_str(value);
+            }
+            JsonEvent::Number(value) => {
+                self.write_u8(1);
+                self.write_str(value);
+            }
+            JsonEvent::Boolean(value) => {
+                self.write_u8(2);
+                self.write_bool(*value);
+            }
+            JsonEvent::Null => self.write_u8(3),
+            JsonEvent::StartArray => self.write_u8(4),
+            JsonEvent::EndArray => self.write_u8(5),
+            JsonEvent::StartObject => self.write_u8(6),
+            JsonEvent::EndObject => self.write_u8(7),
+            JsonEvent::ObjectKey(value) => {
+                self.write_u8(8);
+                self.write_str(value);
+            }
+            JsonEvent::Eof => self.write_u8(9),
+            JsonEvent::StringChunk(value) => {
+                self.write_u8(10);
+                self.write_str(value);
+            }
+            JsonEvent::ObjectKeyChunk(value) => {
+                self.write_u8(11);
+                self.write_str(value);
+            }
+        }
+    }
+
+    pub(crate) fn finish(self) -> Vec<u8> {
+        self.buffer
+    }
+}
+
+/// Reads a snapshot written by [`SnapshotWriter`].
+pub(crate) struct SnapshotReader<'a> {
+    input: &'a [u8],
+}
+
+impl<'a> SnapshotReader<'a> {
+    pub(crate) fn new(snapshot: &'a [u8]) -> io::Result<Self> {
+        let Some(input) = snapshot.strip_prefix(SNAPSHOT_HEADER) else {
+            return Err(invalid_snapshot(
+                "Not a parser snapshot or unsupported format version",
+            ));
+        };
+        Ok(Self { input })
+    }
+
+    pub(crate) fn read_u64(&mut self) -> io::Result<u64> {
+        let mut value = 0;
+        let mut shift = 0;
+        loop {
+            let byte = self.read_u8()?;
+            let bits = u64::from(byte & 0x7F);
+            if shift > 63 || (shift == 63 && bits > 1) {
+                return Err(invalid_snapshot("Integer overflow"));
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+            shift += 7;
+        }
+    }
+
+    pub(crate) fn read_usize(&mut self) -> io::Result<usize> {
+        self.read_u64()?
+            .try_into()
+            .map_err(|_| invalid_snapshot("Integer overflow"))
+    }
+
+    pub(crate) fn read_u8(&mut self) -> io::Result<u8> {
+        let (first, rest) = self
+            .input
+            .split_first()
+            .ok_or_else(|| invalid_snapshot("Unexpected end of snapshot"))?;
+        self.input = rest;
+        Ok(*first)
+    }
+
+    pub(crate) fn read_bool(&mut self) -> io::Result<bool> {
+        match self.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(invalid_snapshot("Invalid boolean")),
+        }
+    }
+
+    pub(crate) fn read_string(&mut self) -> io::Result<String> {
+        let len = self.read_len()?;
+        let (value, rest) = self.input.split_at(len);
+        self.input = rest;
+        String::from_utf8(value.to_vec()).map_err(|_| invalid_snapshot("Invalid UTF-8 string"))
+    }
+
+    pub(crate) fn read_position(&mut self) -> io::Result<TextPosition> {
+        Ok(TextPosition {
+            line: self.read_u64()?,
+            column: self.read_u64()?,
+            offset: self.read_u64()?,
+        })
+    }
+
+    pub(crate) fn read_event(&mut self) -> io::Result<JsonEvent<'static>> {
+        Ok(match self.read_u8()? {
+            0 => JsonEvent::String(self.read_string()?.into()),
+            1 => JsonEvent::Number(self.read_string()?.into()),
+            2 => JsonEvent::Boolean(self.read_bool()?),
+            3 => JsonEvent::Null,
+            4 => JsonEvent::StartArray,
+            5 => JsonEvent::EndArray,
+            6 => JsonEvent::StartObject,
+            7 => JsonEvent::EndObject,
+            8 => JsonEvent::ObjectKey(self.read_string()?.into()),
+            9 => JsonEvent::Eof,
+            10 => JsonEvent::StringChunk(self.read_string()?.into()),
+            11 => JsonEvent::ObjectKeyChunk(self.read_string()?.into()),
+            _ => return Err(invalid_snapshot("Invalid event")),
+        })
+    }
+
+    /// Reads a sequence length, checking that it is not bigger than the remaining input.
+    pub(crate) fn read_len(&mut self) -> io::Result<usize> {
+        let len = self.read_usize()?;
+        if len > self.input.len() {
+            return Err(invalid_snapshot("Unexpected end of snapshot"));
+        }
+        Ok(len)
+    }
+
+    pub(crate) fn finish(self) -> io::Result<()> {
+        if self.input.is_empty() {
+            Ok(())
+        } else {
+            Err(invalid_snapshot(
+                "Unexpected data at the end of the snapshot",
+            ))
+        }
+    }
+}
+
+pub(crate) fn invalid_snapshot(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
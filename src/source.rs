@@ -0,0 +1,222 @@
+use crate::{
+    BufReaderJsonParser, JsonEvent, JsonEventSink, JsonParseError, JsonSyntaxError,
+    ReaderJsonParser, SliceJsonParser,
+};
+#[cfg(feature = "async-tokio")]
+use crate::{TokioAsyncBufReaderJsonParser, TokioAsyncJsonEventSink, TokioAsyncReaderJsonParser};
+#[cfg(feature = "async-tokio")]
+use std::future::Future;
+use std::io::{BufRead, Read};
+#[cfg(feature = "async-tokio")]
+use tokio::io::{AsyncBufRead, AsyncRead};
+
+/// A source of JSON events.
+///
+/// It allows to write code consuming events once and to use it with any parser.
+/// It is implemented by [`SliceJsonParser`], [`ReaderJsonParser`] and [`BufReaderJsonParser`].
+///
+/// ```
+/// use json_event_parser::{JsonEvent, JsonEventSource, ReaderJsonParser, SliceJsonParser};
+///
+/// fn count_numbers<S: JsonEventSource>(mut source: S) -> Result<usize, S::Error> {
+///     let mut count = 0;
+///     loop {
+///         match source.parse_next()? {
+///             JsonEvent::Number(_) => count += 1,
+///             JsonEvent::Eof => return Ok(count),
+///             _ => (),
+///         }
+///     }
+/// }
+///
+/// assert_eq!(count_numbers(SliceJsonParser::new(b"[1, {\"a\": 2}]"))?, 2);
+/// assert_eq!(
+///     count_numbers(ReaderJsonParser::new(b"[1, {\"a\": 2}]".as_slice()))?,
+///     2
+/// );
+/// # std::io::Result::Ok(())
+/// ```
+pub trait JsonEventSource {
+    /// The error returned if the next event can't be produced.
+    type Error;
+
+    /// Returns the next event.
+    fn parse_next(&mut self) -> Result<JsonEvent<'_>, Self::Error>;
+
+    /// The maximal number of nested arrays and objects allowed by the source, if one has been configured.
+    ///
+    /// It is used by [`JsonValue::from_events`](crate::JsonValue::from_events) to bound the depth of the values it builds.
+    /// `None` by default.
+    #[inline]
+    fn max_depth(&self) -> Option<usize> {
+        None
+    }
+}
+
+impl JsonEventSource for SliceJsonParser<'_> {
+    type Error = JsonSyntaxError;
+
+    #[inline]
+    fn parse_next(&mut self) -> Result<JsonEvent<'_>, JsonSyntaxError> {
+        SliceJsonParser::parse_next(self)
+    }
+
+    #[inline]
+    fn max_depth(&self) -> Option<usize> {
+        SliceJsonParser::max_stack_size(self)
+    }
+}
+
+impl<R: Read> JsonEventSource for ReaderJsonParser<R> {
+    type Error = JsonParseError;
+
+    #[inline]
+    fn parse_next(&mut self) -> Result<JsonEvent<'_>, JsonParseError> {
+        ReaderJsonParser::parse_next(self)
+    }
+
+    #[inline]
+    fn max_depth(&self) -> Option<usize> {
+        ReaderJsonParser::max_stack_size(self)
+    }
+}
+
+impl<R: BufRead> JsonEventSource for BufReaderJsonParser<R> {
+    type Error = JsonParseError;
+
+    #[inline]
+    fn parse_next(&mut self) -> Result<JsonEvent<'_>, JsonParseError> {
+        BufReaderJsonParser::parse_next(self)
+    }
+
+    #[inline]
+    fn max_depth(&self) -> Option<usize> {
+        BufReaderJsonParser::max_stack_size(self)
+    }
+}
+
+impl<S: JsonEventSource + ?Sized> JsonEventSource for &mut S {
+    type Error = S::Error;
+
+    #[inline]
+    fn parse_next(&mut self) -> Result<JsonEvent<'_>, S::Error> {
+        (**self).parse_next()
+    }
+
+    #[inline]
+    fn max_depth(&self) -> Option<usize> {
+        (**self).max_depth()
+    }
+}
+
+/// An asynchronous source of JSON events.
+///
+/// It is the asynchronous counterpart of [`JsonEventSource`].
+/// It is implemented by [`TokioAsyncReaderJsonParser`] and [`TokioAsyncBufReaderJsonParser`].
+#[cfg(feature = "async-tokio")]
+pub trait TokioAsyncJsonEventSource {
+    /// The error returned if the next event can't be produced.
+    type Error;
+
+    /// Returns the next event.
+    fn parse_next(&mut self) -> impl Future<Output = Result<JsonEvent<'_>, Self::Error>>;
+}
+
+#[cfg(feature = "async-tokio")]
+impl<R: AsyncRead + Unpin> TokioAsyncJsonEventSource for TokioAsyncReaderJsonParser<R> {
+    type Error = JsonParseError;
+
+    #[inline]
+    fn parse_next(&mut self) -> impl Future<Output = Result<JsonEvent<'_>, JsonParseError>> {
+        TokioAsyncReaderJsonParser::parse_next(self)
+    }
+}
+
+#[cfg(feature = "async-tokio")]
+impl<R: AsyncBufRead + Unpin> TokioAsyncJsonEventSource for TokioAsyncBufReaderJsonParser<R> {
+    type Error = JsonParseError;
+
+    #[inline]
+    fn parse_next(&mut self) -> impl Future<Output = Result<JsonEvent<'_>, JsonParseError>> {
+        TokioAsyncBufReaderJsonParser::parse_next(self)
+    }
+}
+
+#[cfg(feature = "async-tokio")]
+impl<S: TokioAsyncJsonEventSource + ?Sized> TokioAsyncJsonEventSource for &mut S {
+    type Error = S::Error;
+
+    #[inline]
+    fn parse_next(&mut self) -> impl Future<Output = Result<JsonEvent<'_>, S::Error>> {
+        (**self).parse_next()
+    }
+}
+
+/// Reads all the events of `source` until the end of the file and writes them into `sink`.
+///
+/// The sink is not finished, it is up to the caller to call a method like [`WriterJsonSerializer::finish`](crate::WriterJsonSerializer::finish).
+///
+/// ```
+/// use json_event_parser::{pipe_events, SliceJsonParser, WriterJsonSerializer};
+///
+/// let mut serializer = WriterJsonSerializer::new(Vec::new());
+/// pipe_events(
+///     SliceJsonParser::new(b"{\"foo\": [1, true]}"),
+///     &mut serializer,
+/// )?;
+/// assert_eq!(serializer.finish()?.as_slice(), b"{\"foo\":[1,true]}");
+/// # std::io::Result::Ok(())
+/// ```
+pub fn pipe_events<S: JsonEventSource, K: JsonEventSink>(
+    mut source: S,
+    mut sink: K,
+) -> Result<(), JsonParseError>
+where
+    S::Error: Into<JsonParseError>,
+    K::Error: Into<JsonParseError>,
+{
+    loop {
+        match source.parse_next().map_err(Into::into)? {
+            JsonEvent::Eof => return Ok(()),
+            event => sink.serialize_event(event).map_err(Into::into)?,
+        }
+    }
+}
+
+/// Reads all the events of `source` until the end of the file and writes them into `sink`.
+///
+/// It is the asynchronous counterpart of [`pipe_events`].
+///
+/// ```
+/// use json_event_parser::{
+///     tokio_async_pipe_events, TokioAsyncReaderJsonParser, TokioAsyncWriterJsonSerializer,
+/// };
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> ::std::io::Result<()> {
+/// let mut serializer = TokioAsyncWriterJsonSerializer::new(Vec::new());
+/// tokio_async_pipe_events(
+///     TokioAsyncReaderJsonParser::new(b"{\"foo\": [1, true]}".as_slice()),
+///     &mut serializer,
+/// )
+/// .await?;
+/// assert_eq!(serializer.finish()?.as_slice(), b"{\"foo\":[1,true]}");
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "async-tokio")]
+pub async fn tokio_async_pipe_events<S: TokioAsyncJsonEventSource, K: TokioAsyncJsonEventSink>(
+    mut source: S,
+    mut sink: K,
+) -> Result<(), JsonParseError>
+where
+    S::Error: Into<JsonParseError>,
+    K::Error: Into<JsonParseError>,
+{
+    loop {
+        match source.parse_next().await.map_err(Into::into)? {
+            JsonEvent::Eof => return Ok(()),
+            event => sink.serialize_event(event).await.map_err(Into::into)?,
+        }
+    }
+}
@@ -0,0 +1,284 @@
+use crate::value::{parse_json_pointer, parse_json_pointer_index};
+use crate::{JsonEvent, JsonEventSource, JsonParseError, ReaderJsonParser};
+use std::io::{self, Read};
+
+/// Iterates one at a time on the elements of a JSON array read from a [`Read`] implementation.
+///
+/// By default, the array is the root of the document.
+/// An array nested in the document can be selected using [`with_pointer`](Self::with_pointer).
+///
+/// Each element can be read either as a stream of events using [`next_element`](Self::next_element)
+/// or as raw bytes using [`next_raw_element`](Self::next_raw_element).
+/// Only one element is kept in memory at a time.
+///
+/// ```
+/// use json_event_parser::{JsonEvent, ReaderJsonArraySplitter, ReaderJsonParser};
+///
+/// let file = br#"[{"id": 1}, [2], "3"]"#;
+/// let mut splitter = ReaderJsonArraySplitter::new(ReaderJsonParser::new(file.as_slice()));
+/// let mut element = splitter.next_element()?.unwrap();
+/// assert_eq!(element.parse_next()?, JsonEvent::StartObject);
+/// // The rest of the element is skipped when moving to the next one
+/// assert_eq!(splitter.next_raw_element()?.unwrap(), b"[2]");
+/// assert_eq!(splitter.next_raw_element()?.unwrap(), b"\"3\"");
+/// assert!(splitter.next_raw_element()?.is_none());
+/// # std::io::Result::Ok(())
+/// ```
+#[must_use]
+pub struct ReaderJsonArraySplitter<R: Read> {
+    parser: ReaderJsonParser<R>,
+    state: SplitterState,
+    /// Number of open arrays and objects in the current element
+    element_depth: usize,
+    is_in_element: bool,
+}
+
+enum SplitterState {
+    /// The array is not reached yet, it is at the given JSON Pointer
+    Start(String),
+    InArray,
+    End,
+}
+
+impl<R: Read> ReaderJsonArraySplitter<R> {
+    /// Splits the array at the root of the document parsed by `parser`.
+    #[inline]
+    pub fn new(parser: ReaderJsonParser<R>) -> Self {
+        Self {
+            parser,
+            state: SplitterState::Start(String::new()),
+            element_depth: 0,
+            is_in_element: false,
+        }
+    }
+
+    /// Splits the array located at the given [JSON Pointer](https://www.rfc-editor.org/rfc/rfc6901) instead of the root array.
+    ///
+    /// The values before the array are skipped and the parsing stops at the end of the array.
+    /// An error is returned on the first read if the pointer is invalid or does not point to an array.
+    ///
+    /// ```
+    /// use json_event_parser::{ReaderJsonArraySplitter, ReaderJsonParser};
+    ///
+    /// let file = br#"{"meta": {"count": 2}, "data": {"items": [1, 2]}}"#;
+    /// let mut splitter = ReaderJsonArraySplitter::new(ReaderJsonParser::new(file.as_slice()))
+    ///     .with_pointer("/data/items");
+    /// assert_eq!(splitter.next_raw_element()?.unwrap(), b"1");
+    /// assert_eq!(splitter.next_raw_element()?.unwrap(), b"2");
+    /// assert!(splitter.next_raw_element()?.is_none());
+    /// # std::io::Result::Ok(())
+    /// ```
+    #[inline]
+    pub fn with_pointer(mut self, pointer: impl Into<String>) -> Self {
+        if matches!(self.state, SplitterState::Start(_)) {
+            self.state = SplitterState::Start(pointer.into());
+        }
+        self
+    }
+
+    /// Returns a parser on the events of the next element or `None` if the end of the array is reached.
+    ///
+    /// The returned parser returns [`JsonEvent::Eof`] at the end of the element.
+    /// The events of the element that have not been read are skipped by the next call to this method
+    /// or to [`next_raw_element`](Self::next_raw_element).
+    pub fn next_element(&mut self) -> Result<Option<JsonArrayElement<'_, R>>, JsonParseError> {
+        if !self.move_to_next_element()? {
+            return Ok(None);
+        }
+        self.is_in_element = true;
+        Ok(Some(JsonArrayElement { splitter: self }))
+    }
+
+    /// Returns the raw bytes of the next element or `None` if the end of the array is reached.
+    ///
+    /// The bytes are returned as they are in the input, without leading and trailing whitespaces.
+    /// They are validated by the parser.
+    pub fn next_raw_element(&mut self) -> Result<Option<Vec<u8>>, JsonParseError> {
+        self.skip_current_element()?;
+        self.reach_array()?;
+        if matches!(self.state, SplitterState::End) {
+            return Ok(None);
+        }
+        let capture_start = self.parser.start_raw_capture();
+        let result = self.read_raw_element(capture_start);
+        let raw = self.parser.end_raw_capture();
+        Ok(result?.map(|raw_start| raw[raw_start..].to_vec()))
+    }
+
+    /// Returns the underlying parser.
+    ///
+    /// After the end of the array, it can be used to read the rest of the document.
+    #[inline]
+    pub fn into_parser(self) -> ReaderJsonParser<R> {
+        self.parser
+    }
+
+    /// Reads the next element and returns the position of its first byte in the capture buffer.
+    fn read_raw_element(&mut self, capture_start: u64) -> Result<Option<usize>, JsonParseError> {
+        if !self.move_to_next_element()? {
+            return Ok(None);
+        }
+        let raw_start = usize::try_from(self.parser.last_token_start() - capture_start).unwrap();
+        self.is_in_element = true;
+        self.skip_current_element()?;
+        Ok(Some(raw_start))
+    }
+
+    /// Skips what is left of the current element and checks if there is a next one.
+    ///
+    /// If there is one, its first event is buffered in the parser.
+    fn move_to_next_element(&mut self) -> Result<bool, JsonParseError> {
+        self.skip_current_element()?;
+        self.reach_array()?;
+        if matches!(self.state, SplitterState::End) {
+            return Ok(false);
+        }
+        if *self.parser.peek_next()? == JsonEvent::EndArray {
+            self.parser.parse_next()?;
+            self.state = SplitterState::End;
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    fn skip_current_element(&mut self) -> Result<(), JsonParseError> {
+        while self.is_in_element {
+            self.parse_element_event()?;
+        }
+        Ok(())
+    }
+
+    fn parse_element_event(&mut self) -> Result<JsonEvent<'_>, JsonParseError> {
+        if !self.is_in_element {
+            return Ok(JsonEvent::Eof);
+        }
+        let event = self.parser.parse_next()?;
+        match event {
+            JsonEvent::StartArray | JsonEvent::StartObject => self.element_depth += 1,
+            JsonEvent::EndArray | JsonEvent::EndObject => self.element_depth -= 1,
+            JsonEvent::StringChunk(_) | JsonEvent::ObjectKeyChunk(_) => return Ok(event),
+            JsonEvent::Eof => return Err(invalid_data("Unexpected end of file")),
+            _ => (),
+        }
+        if self.element_depth == 0 {
+            self.is_in_element = false;
+        }
+        Ok(event)
+    }
+
+    /// Moves the parser to the start of the array if not done yet.
+    fn reach_array(&mut self) -> Result<(), JsonParseError> {
+        let SplitterState::Start(pointer) = &self.state else {
+            return Ok(());
+        };
+        let pointer = pointer.clone();
+        // If the array is not found, the splitter stops there
+        self.state = SplitterState::End;
+        let Some(tokens) = parse_json_pointer(&pointer) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{pointer} is not a valid JSON Pointer"),
+            )
+            .into());
+        };
+        for token in tokens {
+            match self.parser.parse_next()? {
+                JsonEvent::StartObject => {
+                    // The beginning of the current key if returned in chunks
+                    let mut key_chunks = String::new();
+                    loop {
+                        match self.parser.parse_next()? {
+                            JsonEvent::ObjectKeyChunk(chunk) => key_chunks.push_str(&chunk),
+                            JsonEvent::ObjectKey(key) => {
+                                let is_match = if key_chunks.is_empty() {
+                                    key == token
+                                } else {
+                                    key_chunks.push_str(&key);
+                                    key_chunks == token
+                                };
+                                if is_match {
+                                    break;
+                                }
+                                key_chunks.clear();
+                                self.skip_value()?;
+                            }
+                            _ => return Err(invalid_data("No value found at the JSON Pointer")),
+                        }
+                    }
+                }
+                JsonEvent::StartArray => {
+                    let Some(index) = parse_json_pointer_index(&token) else {
+                        return Err(invalid_data("No value found at the JSON Pointer"));
+                    };
+                    for _ in 0..index {
+                        if *self.parser.peek_next()? == JsonEvent::EndArray {
+                            return Err(invalid_data("No value found at the JSON Pointer"));
+                        }
+                        self.skip_value()?;
+                    }
+                }
+                _ => return Err(invalid_data("No value found at the JSON Pointer")),
+            }
+        }
+        if self.parser.parse_next()? != JsonEvent::StartArray {
+            return Err(invalid_data("The JSON Pointer does not point to an array"));
+        }
+        self.state = SplitterState::InArray;
+        Ok(())
+    }
+
+    fn skip_value(&mut self) -> Result<(), JsonParseError> {
+        self.is_in_element = true;
+        self.skip_current_element()
+    }
+}
+
+/// A parser on the events of an element of an array split by [`ReaderJsonArraySplitter`].
+///
+/// Returns [`JsonEvent::Eof`] at the end of the element.
+///
+/// It implements [`JsonEventSource`] so it can be used to build a [`JsonValue`](crate::JsonValue),
+/// or forwarded to a serializer with [`pipe_events`](crate::pipe_events).
+///
+/// ```
+/// use json_event_parser::{JsonValue, ReaderJsonArraySplitter, ReaderJsonParser};
+///
+/// let file = br#"[{"id": 1}, {"id": 2}]"#;
+/// let mut splitter = ReaderJsonArraySplitter::new(ReaderJsonParser::new(file.as_slice()));
+/// let mut ids = Vec::new();
+/// while let Some(element) = splitter.next_element()? {
+///     ids.push(JsonValue::from_events(element)?["id"].to_string());
+/// }
+/// assert_eq!(ids, ["1", "2"]);
+/// # std::io::Result::Ok(())
+/// ```
+#[must_use]
+pub struct JsonArrayElement<'a, R: Read> {
+    splitter: &'a mut ReaderJsonArraySplitter<R>,
+}
+
+impl<R: Read> JsonArrayElement<'_, R> {
+    /// Returns the next event of the element or [`JsonEvent::Eof`] if the element is finished.
+    #[inline]
+    pub fn parse_next(&mut self) -> Result<JsonEvent<'_>, JsonParseError> {
+        self.splitter.parse_element_event()
+    }
+}
+
+impl<R: Read> JsonEventSource for JsonArrayElement<'_, R> {
+    type Error = JsonParseError;
+
+    #[inline]
+    fn parse_next(&mut self) -> Result<JsonEvent<'_>, JsonParseError> {
+        self.splitter.parse_element_event()
+    }
+
+    #[inline]
+    fn max_depth(&self) -> Option<usize> {
+        self.splitter.parser.max_stack_size()
+    }
+}
+
+fn invalid_data(message: &str) -> JsonParseError {
+    io::Error::new(io::ErrorKind::InvalidData, message).into()
+}
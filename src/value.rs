@@ -0,0 +1,369 @@
+use crate::{JsonEvent, JsonEventSource, JsonParseError, LowLevelJsonSerializer, SliceJsonParser};
+use std::borrow::Cow;
+use std::fmt;
+use std::io;
+use std::ops::Index;
+use std::slice;
+use std::str::FromStr;
+
+/// Maximal number of nested arrays and objects in a [`JsonValue`] built from a source without [depth limit](JsonEventSource::max_depth).
+///
+/// It bounds the recursion depth of the drop, [`Clone`], [`PartialEq`]... implementations.
+const DEFAULT_MAX_DEPTH: usize = 128;
+
+static NULL: JsonValue = JsonValue::Null;
+
+/// An in-memory JSON value.
+///
+/// Objects keep their keys in insertion order and numbers are kept as their textual representation to avoid any loss of precision.
+/// Equality is structural: two objects are equal only if they have the same keys in the same order.
+///
+/// The drop, [`Clone`], [`PartialEq`], [`Hash`] and [`Debug`](fmt::Debug) implementations are recursive.
+/// Their recursion depth is only bounded for values built with [`from_events`](Self::from_events).
+///
+/// ```
+/// use json_event_parser::{JsonValue, SliceJsonParser};
+///
+/// let value = JsonValue::from_events(SliceJsonParser::new(br#"{"a": [1, {"b": true}]}"#))?;
+/// assert_eq!(value["a"][0], JsonValue::Number("1".into()));
+/// assert_eq!(value.pointer("/a/1/b"), Some(&JsonValue::Boolean(true)));
+/// assert_eq!(value["c"], JsonValue::Null);
+/// assert_eq!(value.to_string(), r#"{"a":[1,{"b":true}]}"#);
+/// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+/// ```
+#[derive(Eq, PartialEq, Debug, Clone, Hash, Default)]
+pub enum JsonValue {
+    #[default]
+    Null,
+    Boolean(bool),
+    Number(String),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    /// Builds a value from the next events of `source`.
+    ///
+    /// Only the events of the next value are consumed, so it can be used in the middle of a document.
+    /// The value is built without recursion.
+    /// The number of nested arrays and objects is bounded by the [depth limit of the source](JsonEventSource::max_depth),
+    /// like the one set with [`ReaderJsonParser::with_max_stack_size`](crate::ReaderJsonParser::with_max_stack_size),
+    /// or to 128 if the source has none.
+    /// Large limits allow values that may overflow the stack when they are dropped, cloned or compared.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, JsonValue, ReaderJsonParser};
+    ///
+    /// let mut parser = ReaderJsonParser::new(br#"[{"a": 1}, 2]"#.as_slice());
+    /// assert_eq!(parser.parse_next()?, JsonEvent::StartArray);
+    /// assert_eq!(
+    ///     JsonValue::from_events(&mut parser)?,
+    ///     JsonValue::Object(vec![("a".into(), JsonValue::Number("1".into()))])
+    /// );
+    /// assert_eq!(parser.parse_next()?, JsonEvent::Number("2".into()));
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn from_events<S: JsonEventSource>(mut source: S) -> Result<Self, JsonParseError>
+    where
+        S::Error: Into<JsonParseError>,
+    {
+        let max_depth = source.max_depth().unwrap_or(DEFAULT_MAX_DEPTH);
+        // Containers being built with, for objects, the key of the value being built
+        let mut stack = Vec::<(Self, Option<String>)>::new();
+        // The beginning of a string or a key returned in chunks
+        let mut chunks = None::<String>;
+        loop {
+            let event = source.parse_next().map_err(Into::into)?;
+            if chunks.is_some()
+                && !matches!(
+                    event,
+                    JsonEvent::StringChunk(_)
+                        | JsonEvent::String(_)
+                        | JsonEvent::ObjectKeyChunk(_)
+                        | JsonEvent::ObjectKey(_)
+                )
+            {
+                return Err(invalid_data("Unexpected event inside of a chunked string"));
+            }
+            let value = match event {
+                JsonEvent::StringChunk(chunk) | JsonEvent::ObjectKeyChunk(chunk) => {
+                    chunks.get_or_insert_with(String::new).push_str(&chunk);
+                    continue;
+                }
+                JsonEvent::String(value) => Self::String(with_chunks(chunks.take(), value)),
+                JsonEvent::Number(value) => Self::Number(value.into()),
+                JsonEvent::Boolean(value) => Self::Boolean(value),
+                JsonEvent::Null => Self::Null,
+                JsonEvent::StartArray | JsonEvent::StartObject if stack.len() >= max_depth => {
+                    return Err(invalid_data(format!(
+                        "Too many nested arrays and objects, at most {max_depth} are allowed"
+                    )));
+                }
+                JsonEvent::StartArray => {
+                    stack.push((Self::Array(Vec::new()), None));
+                    continue;
+                }
+                JsonEvent::StartObject => {
+                    stack.push((Self::Object(Vec::new()), None));
+                    continue;
+                }
+                JsonEvent::ObjectKey(key) => {
+                    let Some((Self::Object(_), current_key @ None)) = stack.last_mut() else {
+                        return Err(invalid_data("Unexpected object key"));
+                    };
+                    *current_key = Some(with_chunks(chunks.take(), key));
+                    continue;
+                }
+                JsonEvent::EndArray => match stack.pop() {
+                    Some((value @ Self::Array(_), None)) => value,
+                    _ => return Err(invalid_data("Unexpected array end")),
+                },
+                JsonEvent::EndObject => match stack.pop() {
+                    Some((value @ Self::Object(_), None)) => value,
+                    _ => return Err(invalid_data("Unexpected object end")),
+                },
+                JsonEvent::Eof => return Err(invalid_data("Unexpected end of file")),
+            };
+            match stack.last_mut() {
+                None => return Ok(value),
+                Some((Self::Array(array), None)) => array.push(value),
+                Some((Self::Object(object), key @ Some(_))) => {
+                    object.push((key.take().unwrap(), value));
+                }
+                Some(_) => return Err(invalid_data("Object values must follow a key")),
+            }
+        }
+    }
+
+    /// Returns an iterator on the events of this value, without the final [`JsonEvent::Eof`].
+    ///
+    /// ```
+    /// use json_event_parser::{JsonValue, WriterJsonSerializer};
+    ///
+    /// let value = JsonValue::Array(vec![JsonValue::Null, JsonValue::String("foo".into())]);
+    /// let mut writer = WriterJsonSerializer::new(Vec::new());
+    /// for event in value.to_events() {
+    ///     writer.serialize_event(event)?;
+    /// }
+    /// assert_eq!(writer.finish()?.as_slice(), b"[null,\"foo\"]");
+    /// # std::io::Result::Ok(())
+    /// ```
+    #[inline]
+    pub fn to_events(&self) -> JsonValueEvents<'_> {
+        JsonValueEvents {
+            next: Some(self),
+            stack: Vec::new(),
+        }
+    }
+
+    /// Returns the value associated to `key` if this value is an object containing it.
+    ///
+    /// If the key is present multiple times, the first occurrence is returned.
+    #[inline]
+    pub fn get(&self, key: &str) -> Option<&Self> {
+        let Self::Object(object) = self else {
+            return None;
+        };
+        object.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// Returns the element at position `index` if this value is an array long enough.
+    #[inline]
+    pub fn get_index(&self, index: usize) -> Option<&Self> {
+        let Self::Array(array) = self else {
+            return None;
+        };
+        array.get(index)
+    }
+
+    /// Looks up a value using a [JSON Pointer](https://www.rfc-editor.org/rfc/rfc6901).
+    ///
+    /// Returns `None` if the pointer is invalid or does not match any value.
+    ///
+    /// ```
+    /// use json_event_parser::JsonValue;
+    ///
+    /// let value: JsonValue = r#"{"a/b": [null, {"~c": 1}]}"#.parse()?;
+    /// assert_eq!(value.pointer(""), Some(&value));
+    /// assert_eq!(
+    ///     value.pointer("/a~1b/1/~0c"),
+    ///     Some(&JsonValue::Number("1".into()))
+    /// );
+    /// assert_eq!(value.pointer("/a~1b/01"), None);
+    /// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+    /// ```
+    pub fn pointer(&self, pointer: &str) -> Option<&Self> {
+        let mut current = self;
+        for token in parse_json_pointer(pointer)? {
+            current = match current {
+                Self::Object(_) => current.get(&token)?,
+                Self::Array(_) => current.get_index(parse_json_pointer_index(&token)?)?,
+                _ => return None,
+            }
+        }
+        Some(current)
+    }
+}
+
+impl Index<&str> for JsonValue {
+    type Output = Self;
+
+    /// Returns the value associated to `key` or [`JsonValue::Null`] if there is none.
+    #[inline]
+    fn index(&self, key: &str) -> &Self {
+        self.get(key).unwrap_or(&NULL)
+    }
+}
+
+impl Index<usize> for JsonValue {
+    type Output = Self;
+
+    /// Returns the element at position `index` or [`JsonValue::Null`] if there is none.
+    #[inline]
+    fn index(&self, index: usize) -> &Self {
+        self.get_index(index).unwrap_or(&NULL)
+    }
+}
+
+impl fmt::Display for JsonValue {
+    /// Writes the value as compact JSON.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut serializer = LowLevelJsonSerializer::new();
+        let mut buffer = Vec::new();
+        for event in self.to_events() {
+            serializer
+                .serialize_event(event, &mut buffer)
+                .map_err(|_| fmt::Error)?;
+            f.write_str(std::str::from_utf8(&buffer).map_err(|_| fmt::Error)?)?;
+            buffer.clear();
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for JsonValue {
+    type Err = JsonParseError;
+
+    /// Parses a full JSON document.
+    fn from_str(s: &str) -> Result<Self, JsonParseError> {
+        let mut parser = SliceJsonParser::new(s.as_bytes());
+        let value = Self::from_events(&mut parser)?;
+        match parser.parse_next()? {
+            JsonEvent::Eof => Ok(value),
+            _ => Err(invalid_data("Unexpected content after the JSON value")),
+        }
+    }
+}
+
+/// Iterator on the events of a [`JsonValue`].
+///
+/// Can be built using [`JsonValue::to_events`].
+#[must_use]
+pub struct JsonValueEvents<'a> {
+    next: Option<&'a JsonValue>,
+    stack: Vec<ContainerIter<'a>>,
+}
+
+enum ContainerIter<'a> {
+    Array(slice::Iter<'a, JsonValue>),
+    Object(slice::Iter<'a, (String, JsonValue)>),
+}
+
+impl<'a> Iterator for JsonValueEvents<'a> {
+    type Item = JsonEvent<'a>;
+
+    fn next(&mut self) -> Option<JsonEvent<'a>> {
+        if let Some(value) = self.next.take() {
+            return Some(match value {
+                JsonValue::Null => JsonEvent::Null,
+                JsonValue::Boolean(value) => JsonEvent::Boolean(*value),
+                JsonValue::Number(value) => JsonEvent::Number(Cow::Borrowed(value)),
+                JsonValue::String(value) => JsonEvent::String(Cow::Borrowed(value)),
+                JsonValue::Array(array) => {
+                    self.stack.push(ContainerIter::Array(array.iter()));
+                    JsonEvent::StartArray
+                }
+                JsonValue::Object(object) => {
+                    self.stack.push(ContainerIter::Object(object.iter()));
+                    JsonEvent::StartObject
+                }
+            });
+        }
+        Some(match self.stack.last_mut()? {
+            ContainerIter::Array(iter) => {
+                if let Some(value) = iter.next() {
+                    self.next = Some(value);
+                    return self.next();
+                }
+                self.stack.pop();
+                JsonEvent::EndArray
+            }
+            ContainerIter::Object(iter) => {
+                if let Some((key, value)) = iter.next() {
+                    self.next = Some(value);
+                    return Some(JsonEvent::ObjectKey(Cow::Borrowed(key)));
+                }
+                self.stack.pop();
+                JsonEvent::EndObject
+            }
+        })
+    }
+}
+
+/// Splits a [JSON Pointer](https://www.rfc-editor.org/rfc/rfc6901) into its unescaped reference tokens.
+///
+/// Returns `None` if the pointer is not valid.
+pub(crate) fn parse_json_pointer(pointer: &str) -> Option<Vec<Cow<'_, str>>> {
+    if pointer.is_empty() {
+        return Some(Vec::new());
+    }
+    pointer
+        .strip_prefix('/')?
+        .split('/')
+        .map(|token| {
+            if !token.contains('~') {
+                return Some(Cow::Borrowed(token));
+            }
+            let mut unescaped = String::with_capacity(token.len());
+            let mut chars = token.chars();
+            while let Some(c) = chars.next() {
+                if c == '~' {
+                    unescaped.push(match chars.next()? {
+                        '0' => '~',
+                        '1' => '/',
+                        _ => return None,
+                    });
+                } else {
+                    unescaped.push(c);
+                }
+            }
+            Some(Cow::Owned(unescaped))
+        })
+        .collect()
+}
+
+/// Parses a JSON Pointer array index, rejecting leading zeros and the `-` past-the-end index.
+pub(crate) fn parse_json_pointer_index(token: &str) -> Option<usize> {
+    if token.is_empty()
+        || (token.len() > 1 && token.starts_with('0'))
+        || !token.bytes().all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    token.parse().ok()
+}
+
+/// Appends `end` to the already read chunks if any.
+fn with_chunks(chunks: Option<String>, end: Cow<'_, str>) -> String {
+    if let Some(mut chunks) = chunks {
+        chunks.push_str(&end);
+        chunks
+    } else {
+        end.into()
+    }
+}
+
+fn invalid_data(message: impl Into<String>) -> JsonParseError {
+    io::Error::new(io::ErrorKind::InvalidData, message.into()).into()
+}
@@ -1,5 +1,11 @@
-use crate::JsonEvent;
+use crate::base64::Base64Encoder;
+use crate::read::{check_i_json_event, ObjectKeysTracker, MAX_TRACKED_OBJECT_KEYS_SIZE};
+use crate::{
+    Base64Alphabet, JsonEvent, LoneSurrogate, LowLevelJsonSerializerSink, SliceJsonParser,
+};
+use std::borrow::Cow;
 use std::io::{Error, ErrorKind, Result, Write};
+use std::mem;
 #[cfg(feature = "async-tokio")]
 use tokio::io::{AsyncWrite, AsyncWriteExt};
 
@@ -18,8 +24,8 @@ use tokio::io::{AsyncWrite, AsyncWriteExt};
 /// # std::io::Result::Ok(())
 /// ```
 pub struct WriterJsonSerializer<W: Write> {
-    write: W,
-    writer: LowLevelJsonSerializer,
+    pub(crate) write: W,
+    pub(crate) writer: LowLevelJsonSerializer,
 }
 
 impl<W: Write> WriterJsonSerializer<W> {
@@ -30,6 +36,33 @@ impl<W: Write> WriterJsonSerializer<W> {
         }
     }
 
+    /// Sets the alphabet used to encode bytes in base64.
+    ///
+    /// See [`LowLevelJsonSerializer::with_base64_alphabet`].
+    #[inline]
+    pub fn with_base64_alphabet(mut self, alphabet: Base64Alphabet) -> Self {
+        self.writer = self.writer.with_base64_alphabet(alphabet);
+        self
+    }
+
+    /// Sets if the base64 encoded strings are padded with `=`.
+    ///
+    /// See [`LowLevelJsonSerializer::with_base64_padding`].
+    #[inline]
+    pub fn with_base64_padding(mut self, padding: bool) -> Self {
+        self.writer = self.writer.with_base64_padding(padding);
+        self
+    }
+
+    /// Validates that the output follows the [I-JSON](https://www.rfc-editor.org/rfc/rfc7493) profile.
+    ///
+    /// See [`LowLevelJsonSerializer::with_i_json_validation`] for details.
+    #[inline]
+    pub fn with_i_json_validation(mut self) -> Self {
+        self.writer = self.writer.with_i_json_validation();
+        self
+    }
+
     pub fn serialize_event(&mut self, event: JsonEvent<'_>) -> Result<()> {
         self.writer.serialize_event(event, &mut self.write)
     }
@@ -39,6 +72,150 @@ impl<W: Write> WriterJsonSerializer<W> {
         self.serialize_event(event)
     }
 
+    /// Writes a string or object key event containing lone surrogates replaced by U+FFFD.
+    ///
+    /// See [`LowLevelJsonSerializer::serialize_event_with_lone_surrogates`].
+    pub fn serialize_event_with_lone_surrogates(
+        &mut self,
+        event: JsonEvent<'_>,
+        lone_surrogates: &[LoneSurrogate],
+    ) -> Result<()> {
+        self.writer
+            .serialize_event_with_lone_surrogates(event, lone_surrogates, &mut self.write)
+    }
+
+    /// Writes an already serialized JSON value as it is.
+    ///
+    /// See [`LowLevelJsonSerializer::serialize_raw_value`].
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, WriterJsonSerializer};
+    ///
+    /// let mut writer = WriterJsonSerializer::new(Vec::new());
+    /// writer.serialize_event(JsonEvent::StartArray)?;
+    /// writer.serialize_event(JsonEvent::Number("1".into()))?;
+    /// writer.serialize_raw_value(b"{\"foo\": [true]}")?;
+    /// writer.serialize_event(JsonEvent::EndArray)?;
+    /// assert_eq!(writer.finish()?.as_slice(), b"[1,{\"foo\": [true]}]");
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn serialize_raw_value(&mut self, value: &[u8]) -> Result<()> {
+        self.writer.serialize_raw_value(value, &mut self.write)
+    }
+
+    /// Writes an already serialized JSON value as it is without checking it is valid JSON.
+    ///
+    /// See [`LowLevelJsonSerializer::serialize_unvalidated_raw_value`].
+    pub fn serialize_unvalidated_raw_value(&mut self, value: &[u8]) -> Result<()> {
+        self.writer
+            .serialize_unvalidated_raw_value(value, &mut self.write)
+    }
+
+    /// Starts a string value whose content is then written in chunks.
+    ///
+    /// See [`LowLevelJsonSerializer::start_string`].
+    ///
+    /// ```
+    /// use json_event_parser::WriterJsonSerializer;
+    ///
+    /// let mut writer = WriterJsonSerializer::new(Vec::new());
+    /// writer.start_string()?;
+    /// for line in ["a", "b", "c"] {
+    ///     writer.serialize_string_chunk(line)?;
+    ///     writer.serialize_string_chunk("\n")?;
+    /// }
+    /// writer.end_string()?;
+    /// assert_eq!(writer.finish()?.as_slice(), b"\"a\\nb\\nc\\n\"");
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn start_string(&mut self) -> Result<()> {
+        self.writer.start_string(&mut self.write)
+    }
+
+    /// Writes a chunk of the string or of the object key currently written.
+    ///
+    /// See [`LowLevelJsonSerializer::serialize_string_chunk`].
+    pub fn serialize_string_chunk(&mut self, chunk: &str) -> Result<()> {
+        self.writer.serialize_string_chunk(chunk, &mut self.write)
+    }
+
+    /// Ends a string value started with [`start_string`](Self::start_string).
+    ///
+    /// See [`LowLevelJsonSerializer::end_string`].
+    pub fn end_string(&mut self) -> Result<()> {
+        self.writer.end_string(&mut self.write)
+    }
+
+    /// Starts an object key whose content is then written in chunks.
+    ///
+    /// See [`LowLevelJsonSerializer::start_object_key`].
+    pub fn start_object_key(&mut self) -> Result<()> {
+        self.writer.start_object_key(&mut self.write)
+    }
+
+    /// Ends an object key started with [`start_object_key`](Self::start_object_key).
+    ///
+    /// See [`LowLevelJsonSerializer::end_object_key`].
+    pub fn end_object_key(&mut self) -> Result<()> {
+        self.writer.end_object_key(&mut self.write)
+    }
+
+    /// Writes bytes as a base64 encoded string value.
+    ///
+    /// See [`LowLevelJsonSerializer::serialize_base64`].
+    pub fn serialize_base64(&mut self, value: &[u8]) -> Result<()> {
+        self.writer.serialize_base64(value, &mut self.write)
+    }
+
+    /// Starts a string value whose content is raw bytes encoded in base64 on the fly.
+    ///
+    /// See [`LowLevelJsonSerializer::start_base64_string`].
+    pub fn start_base64_string(&mut self) -> Result<()> {
+        self.writer.start_base64_string(&mut self.write)
+    }
+
+    /// Encodes in base64 and writes a chunk of the bytes of the string currently written.
+    ///
+    /// See [`LowLevelJsonSerializer::serialize_base64_chunk`].
+    pub fn serialize_base64_chunk(&mut self, chunk: &[u8]) -> Result<()> {
+        self.writer.serialize_base64_chunk(chunk, &mut self.write)
+    }
+
+    /// Ends a string started with [`start_base64_string`](Self::start_base64_string).
+    ///
+    /// See [`LowLevelJsonSerializer::end_base64_string`].
+    pub fn end_base64_string(&mut self) -> Result<()> {
+        self.writer.end_base64_string(&mut self.write)
+    }
+
+    /// Resets the serializer to write a new document to the same output.
+    ///
+    /// See [`LowLevelJsonSerializer::reset`] for details.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.writer.reset();
+    }
+
+    /// Resets the serializer to write a new document to `write` and returns the previous output.
+    ///
+    /// Unlike [`finish`](Self::finish), it does not check that the previous document is complete.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, WriterJsonSerializer};
+    ///
+    /// let mut writer = WriterJsonSerializer::new(Vec::new());
+    /// writer.serialize_event(JsonEvent::StartArray)?;
+    /// writer.serialize_event(JsonEvent::EndArray)?;
+    /// assert_eq!(writer.reset_with_writer(Vec::new()), b"[]");
+    /// writer.serialize_event(JsonEvent::Null)?;
+    /// assert_eq!(writer.finish()?, b"null");
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn reset_with_writer(&mut self, write: W) -> W {
+        self.writer.reset();
+        mem::replace(&mut self.write, write)
+    }
+
     pub fn finish(self) -> Result<W> {
         self.writer.validate_eof()?;
         Ok(self.write)
@@ -67,9 +244,9 @@ impl<W: Write> WriterJsonSerializer<W> {
 /// ```
 #[cfg(feature = "async-tokio")]
 pub struct TokioAsyncWriterJsonSerializer<W: AsyncWrite + Unpin> {
-    write: W,
-    writer: LowLevelJsonSerializer,
-    buffer: Vec<u8>,
+    pub(crate) write: W,
+    pub(crate) writer: LowLevelJsonSerializer,
+    pub(crate) buffer: Vec<u8>,
 }
 
 #[cfg(feature = "async-tokio")]
@@ -82,6 +259,33 @@ impl<W: AsyncWrite + Unpin> TokioAsyncWriterJsonSerializer<W> {
         }
     }
 
+    /// Sets the alphabet used to encode bytes in base64.
+    ///
+    /// See [`LowLevelJsonSerializer::with_base64_alphabet`].
+    #[inline]
+    pub fn with_base64_alphabet(mut self, alphabet: Base64Alphabet) -> Self {
+        self.writer = self.writer.with_base64_alphabet(alphabet);
+        self
+    }
+
+    /// Sets if the base64 encoded strings are padded with `=`.
+    ///
+    /// See [`LowLevelJsonSerializer::with_base64_padding`].
+    #[inline]
+    pub fn with_base64_padding(mut self, padding: bool) -> Self {
+        self.writer = self.writer.with_base64_padding(padding);
+        self
+    }
+
+    /// Validates that the output follows the [I-JSON](https://www.rfc-editor.org/rfc/rfc7493) profile.
+    ///
+    /// See [`LowLevelJsonSerializer::with_i_json_validation`] for details.
+    #[inline]
+    pub fn with_i_json_validation(mut self) -> Self {
+        self.writer = self.writer.with_i_json_validation();
+        self
+    }
+
     pub async fn serialize_event(&mut self, event: JsonEvent<'_>) -> Result<()> {
         self.writer.serialize_event(event, &mut self.buffer)?;
         self.write.write_all(&self.buffer).await?;
@@ -94,6 +298,154 @@ impl<W: AsyncWrite + Unpin> TokioAsyncWriterJsonSerializer<W> {
         self.serialize_event(event).await
     }
 
+    /// Writes a string or object key event containing lone surrogates replaced by U+FFFD.
+    ///
+    /// See [`LowLevelJsonSerializer::serialize_event_with_lone_surrogates`].
+    pub async fn serialize_event_with_lone_surrogates(
+        &mut self,
+        event: JsonEvent<'_>,
+        lone_surrogates: &[LoneSurrogate],
+    ) -> Result<()> {
+        self.writer.serialize_event_with_lone_surrogates(
+            event,
+            lone_surrogates,
+            &mut self.buffer,
+        )?;
+        self.write.write_all(&self.buffer).await?;
+        self.buffer.clear();
+        Ok(())
+    }
+
+    /// Writes an already serialized JSON value as it is.
+    ///
+    /// See [`LowLevelJsonSerializer::serialize_raw_value`].
+    pub async fn serialize_raw_value(&mut self, value: &[u8]) -> Result<()> {
+        self.writer.serialize_raw_value(value, &mut self.buffer)?;
+        self.write.write_all(&self.buffer).await?;
+        self.buffer.clear();
+        Ok(())
+    }
+
+    /// Writes an already serialized JSON value as it is without checking it is valid JSON.
+    ///
+    /// See [`LowLevelJsonSerializer::serialize_unvalidated_raw_value`].
+    pub async fn serialize_unvalidated_raw_value(&mut self, value: &[u8]) -> Result<()> {
+        self.writer
+            .serialize_unvalidated_raw_value(value, &mut self.buffer)?;
+        self.write.write_all(&self.buffer).await?;
+        self.buffer.clear();
+        Ok(())
+    }
+
+    /// Starts a string value whose content is then written in chunks.
+    ///
+    /// See [`LowLevelJsonSerializer::start_string`].
+    pub async fn start_string(&mut self) -> Result<()> {
+        self.writer.start_string(&mut self.buffer)?;
+        self.write.write_all(&self.buffer).await?;
+        self.buffer.clear();
+        Ok(())
+    }
+
+    /// Writes a chunk of the string or of the object key currently written.
+    ///
+    /// See [`LowLevelJsonSerializer::serialize_string_chunk`].
+    pub async fn serialize_string_chunk(&mut self, chunk: &str) -> Result<()> {
+        self.writer
+            .serialize_string_chunk(chunk, &mut self.buffer)?;
+        self.write.write_all(&self.buffer).await?;
+        self.buffer.clear();
+        Ok(())
+    }
+
+    /// Ends a string value started with [`start_string`](Self::start_string).
+    ///
+    /// See [`LowLevelJsonSerializer::end_string`].
+    pub async fn end_string(&mut self) -> Result<()> {
+        self.writer.end_string(&mut self.buffer)?;
+        self.write.write_all(&self.buffer).await?;
+        self.buffer.clear();
+        Ok(())
+    }
+
+    /// Starts an object key whose content is then written in chunks.
+    ///
+    /// See [`LowLevelJsonSerializer::start_object_key`].
+    pub async fn start_object_key(&mut self) -> Result<()> {
+        self.writer.start_object_key(&mut self.buffer)?;
+        self.write.write_all(&self.buffer).await?;
+        self.buffer.clear();
+        Ok(())
+    }
+
+    /// Ends an object key started with [`start_object_key`](Self::start_object_key).
+    ///
+    /// See [`LowLevelJsonSerializer::end_object_key`].
+    pub async fn end_object_key(&mut self) -> Result<()> {
+        self.writer.end_object_key(&mut self.buffer)?;
+        self.write.write_all(&self.buffer).await?;
+        self.buffer.clear();
+        Ok(())
+    }
+
+    /// Writes bytes as a base64 encoded string value.
+    ///
+    /// See [`LowLevelJsonSerializer::serialize_base64`].
+    pub async fn serialize_base64(&mut self, value: &[u8]) -> Result<()> {
+        self.writer.serialize_base64(value, &mut self.buffer)?;
+        self.write.write_all(&self.buffer).await?;
+        self.buffer.clear();
+        Ok(())
+    }
+
+    /// Starts a string value whose content is raw bytes encoded in base64 on the fly.
+    ///
+    /// See [`LowLevelJsonSerializer::start_base64_string`].
+    pub async fn start_base64_string(&mut self) -> Result<()> {
+        self.writer.start_base64_string(&mut self.buffer)?;
+        self.write.write_all(&self.buffer).await?;
+        self.buffer.clear();
+        Ok(())
+    }
+
+    /// Encodes in base64 and writes a chunk of the bytes of the string currently written.
+    ///
+    /// See [`LowLevelJsonSerializer::serialize_base64_chunk`].
+    pub async fn serialize_base64_chunk(&mut self, chunk: &[u8]) -> Result<()> {
+        self.writer
+            .serialize_base64_chunk(chunk, &mut self.buffer)?;
+        self.write.write_all(&self.buffer).await?;
+        self.buffer.clear();
+        Ok(())
+    }
+
+    /// Ends a string started with [`start_base64_string`](Self::start_base64_string).
+    ///
+    /// See [`LowLevelJsonSerializer::end_base64_string`].
+    pub async fn end_base64_string(&mut self) -> Result<()> {
+        self.writer.end_base64_string(&mut self.buffer)?;
+        self.write.write_all(&self.buffer).await?;
+        self.buffer.clear();
+        Ok(())
+    }
+
+    /// Resets the serializer to write a new document to the same output.
+    ///
+    /// See [`LowLevelJsonSerializer::reset`] for details.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.writer.reset();
+        self.buffer.clear();
+    }
+
+    /// Resets the serializer to write a new document to `write` and returns the previous output.
+    ///
+    /// See [`WriterJsonSerializer::reset_with_writer`] for details.
+    pub fn reset_with_writer(&mut self, write: W) -> W {
+        self.reset();
+        mem::replace(&mut self.write, write)
+    }
+
     pub fn finish(self) -> Result<W> {
         self.writer.validate_eof()?;
         Ok(self.write)
@@ -122,6 +474,9 @@ impl<W: AsyncWrite + Unpin> TokioAsyncWriterJsonSerializer<W> {
 pub struct LowLevelJsonSerializer {
     state_stack: Vec<JsonState>,
     element_written: bool,
+    base64_encoder: Base64Encoder,
+    /// Set if the I-JSON profile is enforced
+    i_json_object_keys: Option<ObjectKeysTracker>,
 }
 
 impl LowLevelJsonSerializer {
@@ -129,15 +484,203 @@ impl LowLevelJsonSerializer {
         Self {
             state_stack: Vec::new(),
             element_written: false,
+            base64_encoder: Base64Encoder::new(),
+            i_json_object_keys: None,
+        }
+    }
+
+    /// Validates that the output follows the [I-JSON](https://www.rfc-editor.org/rfc/rfc7493) profile. Disabled by default.
+    ///
+    /// An error is returned, and nothing is written, on duplicate object keys,
+    /// numbers that are not representable as IEEE 754 double precision numbers,
+    /// integers outside of the [-(2<sup>53</sup>)+1, 2<sup>53</sup>-1] range
+    /// and strings or object keys containing Unicode noncharacters.
+    /// See [`LowLevelJsonParser::with_i_json_validation`](crate::LowLevelJsonParser::with_i_json_validation) for details.
+    ///
+    /// When an object key is written in chunks, the duplicate key error is only returned at its end, after its content has been written.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, LowLevelJsonSerializer};
+    ///
+    /// let mut writer = LowLevelJsonSerializer::new().with_i_json_validation();
+    /// let mut output = Vec::new();
+    /// writer.serialize_event(JsonEvent::StartObject, &mut output)?;
+    /// writer.serialize_event(JsonEvent::ObjectKey("a".into()), &mut output)?;
+    /// assert!(writer
+    ///     .serialize_event(JsonEvent::Number("1e400".into()), &mut output)
+    ///     .is_err());
+    /// writer.serialize_event(JsonEvent::Number("1".into()), &mut output)?;
+    /// assert!(writer
+    ///     .serialize_event(JsonEvent::ObjectKey("a".into()), &mut output)
+    ///     .is_err());
+    /// writer.serialize_event(JsonEvent::EndObject, &mut output)?;
+    ///
+    /// assert_eq!(output.as_slice(), b"{\"a\":1}");
+    /// # std::io::Result::Ok(())
+    /// ```
+    #[inline]
+    pub fn with_i_json_validation(mut self) -> Self {
+        if self.i_json_object_keys.is_none() {
+            self.i_json_object_keys = Some(ObjectKeysTracker::new(MAX_TRACKED_OBJECT_KEYS_SIZE));
+        }
+        self
+    }
+
+    /// Sets the alphabet used to encode bytes in base64 (the [standard one](Base64Alphabet::Standard) by default).
+    ///
+    /// ```
+    /// use json_event_parser::{Base64Alphabet, LowLevelJsonSerializer};
+    ///
+    /// let mut writer = LowLevelJsonSerializer::new()
+    ///     .with_base64_alphabet(Base64Alphabet::UrlSafe)
+    ///     .with_base64_padding(false);
+    /// let mut output = Vec::new();
+    /// writer.serialize_base64(b"\xFB\xFF", &mut output)?;
+    /// assert_eq!(output.as_slice(), b"\"-_8\"");
+    /// # std::io::Result::Ok(())
+    /// ```
+    #[inline]
+    pub fn with_base64_alphabet(mut self, alphabet: Base64Alphabet) -> Self {
+        self.base64_encoder.set_alphabet(alphabet);
+        self
+    }
+
+    /// Sets if the base64 encoded strings are padded with `=` to a multiple of 4 characters (`true` by default).
+    #[inline]
+    pub fn with_base64_padding(mut self, padding: bool) -> Self {
+        self.base64_encoder.set_padding(padding);
+        self
+    }
+
+    /// Resets the serializer to write a new document.
+    ///
+    /// The open arrays and objects, the pending base64 bytes and the keys tracked by the [I-JSON validation](Self::with_i_json_validation) are cleared,
+    /// without checking that the previous document is complete.
+    /// The configuration and the allocated memory are kept.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, LowLevelJsonSerializer};
+    ///
+    /// let mut writer = LowLevelJsonSerializer::new();
+    /// let mut output = Vec::new();
+    /// writer.serialize_event(JsonEvent::StartArray, &mut output)?;
+    /// writer.reset();
+    /// output.clear();
+    /// writer.serialize_event(JsonEvent::Null, &mut output)?;
+    /// assert_eq!(output.as_slice(), b"null");
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn reset(&mut self) {
+        self.state_stack.clear();
+        self.element_written = false;
+        self.base64_encoder.reset();
+        if let Some(object_keys) = &mut self.i_json_object_keys {
+            object_keys.reset();
+        }
+    }
+
+    /// Pairs the serializer with `write` to use them as a [`JsonEventSink`](crate::JsonEventSink).
+    #[inline]
+    pub fn sink<W: Write>(&mut self, write: W) -> LowLevelJsonSerializerSink<'_, W> {
+        LowLevelJsonSerializerSink {
+            serializer: self,
+            write,
+        }
+    }
+
+    pub fn serialize_event(&mut self, event: JsonEvent<'_>, write: impl Write) -> Result<()> {
+        if self.i_json_object_keys.is_none() {
+            return self.write_json_event(event, write);
         }
+        check_i_json_event(&event).map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
+        let object_event = match event {
+            JsonEvent::ObjectKey(_) | JsonEvent::ObjectKeyChunk(_) => {
+                if matches!(
+                    self.state_stack.last(),
+                    Some(
+                        JsonState::OpenObject
+                            | JsonState::ContinuationObject
+                            | JsonState::InObjectKey
+                    )
+                ) {
+                    // The key is going to be written
+                    self.track_i_json_object_key_event(&event)?;
+                }
+                None
+            }
+            JsonEvent::StartObject => Some(JsonEvent::StartObject),
+            JsonEvent::EndObject => Some(JsonEvent::EndObject),
+            _ => None,
+        };
+        self.write_json_event(event, write)?;
+        if let Some(object_event) = object_event {
+            self.track_i_json_object_key_event(&object_event)?;
+        }
+        Ok(())
+    }
+
+    fn track_i_json_object_key_event(&mut self, event: &JsonEvent<'_>) -> Result<()> {
+        if let Some(object_keys) = &mut self.i_json_object_keys {
+            object_keys
+                .on_event(event, 0)
+                .map_err(|(_, e)| Error::new(ErrorKind::InvalidInput, e))?;
+        }
+        Ok(())
     }
 
-    pub fn serialize_event(&mut self, event: JsonEvent<'_>, mut write: impl Write) -> Result<()> {
+    fn write_json_event(&mut self, event: JsonEvent<'_>, mut write: impl Write) -> Result<()> {
+        match self.state_stack.last() {
+            Some(JsonState::InString) => {
+                return match event {
+                    JsonEvent::StringChunk(chunk) => {
+                        write_escaped_json_string_content(&chunk, write)
+                    }
+                    JsonEvent::String(end) => {
+                        self.state_stack.pop();
+                        write_escaped_json_string_content(&end, &mut write)?;
+                        write.write_all(b"\"")
+                    }
+                    _ => Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "A string chunk must be followed by other chunks or the string end",
+                    )),
+                }
+            }
+            Some(JsonState::InObjectKey) => {
+                return match event {
+                    JsonEvent::ObjectKeyChunk(chunk) => {
+                        write_escaped_json_string_content(&chunk, write)
+                    }
+                    JsonEvent::ObjectKey(end) => {
+                        self.state_stack.pop();
+                        write_escaped_json_string_content(&end, &mut write)?;
+                        write.write_all(b"\":")
+                    }
+                    _ => Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "An object key chunk must be followed by other chunks or the key end",
+                    )),
+                }
+            }
+            Some(JsonState::InBase64String) => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "A base64 string is being written, it must be ended first",
+                ))
+            }
+            _ => (),
+        }
         match event {
             JsonEvent::String(s) => {
                 self.before_value(&mut write)?;
                 write_escaped_json_string(&s, write)
             }
+            JsonEvent::StringChunk(chunk) => {
+                self.before_value(&mut write)?;
+                self.state_stack.push(JsonState::InString);
+                write.write_all(b"\"")?;
+                write_escaped_json_string_content(&chunk, write)
+            }
             JsonEvent::Number(number) => {
                 self.before_value(&mut write)?;
                 write.write_all(number.as_bytes())
@@ -193,21 +736,16 @@ impl LowLevelJsonSerializer {
                 )),
             },
             JsonEvent::ObjectKey(key) => {
-                match self.state_stack.pop() {
-                    Some(JsonState::OpenObject) => (),
-                    Some(JsonState::ContinuationObject) => write.write_all(b",")?,
-                    _ => {
-                        return Err(Error::new(
-                            ErrorKind::InvalidInput,
-                            "Trying to write an object key in an not object",
-                        ))
-                    }
-                }
-                self.state_stack.push(JsonState::ContinuationObject);
-                self.state_stack.push(JsonState::ObjectValue);
+                self.before_object_key(&mut write)?;
                 write_escaped_json_string(&key, &mut write)?;
                 write.write_all(b":")
             }
+            JsonEvent::ObjectKeyChunk(chunk) => {
+                self.before_object_key(&mut write)?;
+                self.state_stack.push(JsonState::InObjectKey);
+                write.write_all(b"\"")?;
+                write_escaped_json_string_content(&chunk, write)
+            }
             JsonEvent::Eof => Err(Error::new(
                 ErrorKind::InvalidInput,
                 "EOF is not allowed in JSON writer",
@@ -220,6 +758,343 @@ impl LowLevelJsonSerializer {
         self.serialize_event(event, write)
     }
 
+    /// Writes a string or object key event containing lone surrogates replaced by U+FFFD.
+    ///
+    /// The U+FFFD characters at the positions given by `lone_surrogates` are written as the `\uXXXX` escape sequences of the surrogates,
+    /// using their [`hex_digits`](LoneSurrogate::hex_digits) to keep the case of the input,
+    /// making lossless the round trip with [`LowLevelJsonParser::with_lone_surrogate_preservation`](crate::LowLevelJsonParser::with_lone_surrogate_preservation).
+    /// If `lone_surrogates` is empty, it is equivalent to [`serialize_event`](Self::serialize_event).
+    ///
+    /// Lone surrogates are not allowed by [I-JSON](Self::with_i_json_validation).
+    ///
+    /// ```
+    /// use json_event_parser::{LowLevelJsonSerializer, SliceJsonParser};
+    ///
+    /// let input = br#"{"a\uD888": ["\uDE00b"]}"#;
+    /// let mut reader = SliceJsonParser::new(input).with_lone_surrogate_preservation();
+    /// let mut writer = LowLevelJsonSerializer::new();
+    /// let mut output = Vec::new();
+    /// for event in 0..6 {
+    ///     let event = reader.parse_next()?;
+    ///     writer.serialize_event_with_lone_surrogates(event, reader.lone_surrogates(), &mut output)?;
+    /// }
+    /// assert_eq!(output.as_slice(), br#"{"a\uD888":["\uDE00b"]}"#);
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn serialize_event_with_lone_surrogates(
+        &mut self,
+        event: JsonEvent<'_>,
+        lone_surrogates: &[LoneSurrogate],
+        mut write: impl Write,
+    ) -> Result<()> {
+        if lone_surrogates.is_empty() {
+            return self.serialize_event(event, write);
+        }
+        let (content, is_key, is_end) = match &event {
+            JsonEvent::String(content) => (content, false, true),
+            JsonEvent::StringChunk(content) => (content, false, false),
+            JsonEvent::ObjectKey(content) => (content, true, true),
+            JsonEvent::ObjectKeyChunk(content) => (content, true, false),
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "Only strings and object keys might contain lone surrogates",
+                ))
+            }
+        };
+        if self.i_json_object_keys.is_some() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Lone surrogates are not allowed by I-JSON",
+            ));
+        }
+        let mut previous_end = 0;
+        for lone_surrogate in lone_surrogates {
+            if lone_surrogate.offset < previous_end
+                || !(0xD800..=0xDFFF).contains(&lone_surrogate.code_unit)
+                || !lone_surrogate.has_matching_hex_digits()
+                || !content
+                    .get(lone_surrogate.offset..)
+                    .is_some_and(|c| c.starts_with(char::REPLACEMENT_CHARACTER))
+            {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "The lone surrogate \\u{:X} does not match a U+FFFD character at position {} of the string",
+                        lone_surrogate.code_unit, lone_surrogate.offset
+                    ),
+                ));
+            }
+            previous_end = lone_surrogate.offset + char::REPLACEMENT_CHARACTER.len_utf8();
+        }
+        match (self.state_stack.last(), is_key) {
+            (Some(JsonState::InString), false) | (Some(JsonState::InObjectKey), true) => (),
+            (_, false) => self.start_string(&mut write)?,
+            (_, true) => self.start_object_key(&mut write)?,
+        }
+        let mut start = 0;
+        for lone_surrogate in lone_surrogates {
+            write_escaped_json_string_content(&content[start..lone_surrogate.offset], &mut write)?;
+            write.write_all(b"\\u")?;
+            write.write_all(&lone_surrogate.hex_digits)?;
+            start = lone_surrogate.offset + char::REPLACEMENT_CHARACTER.len_utf8();
+        }
+        write_escaped_json_string_content(&content[start..], &mut write)?;
+        match (is_end, is_key) {
+            (false, _) => Ok(()),
+            (true, false) => self.end_string(write),
+            (true, true) => self.end_object_key(write),
+        }
+    }
+
+    /// Writes an already serialized JSON value as it is.
+    ///
+    /// The value is parsed first to make sure it is a single well-formed JSON value.
+    /// Use [`serialize_unvalidated_raw_value`](Self::serialize_unvalidated_raw_value) to skip this check.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, LowLevelJsonSerializer};
+    ///
+    /// let mut writer = LowLevelJsonSerializer::new();
+    /// let mut output = Vec::new();
+    /// writer.serialize_event(JsonEvent::StartObject, &mut output)?;
+    /// writer.serialize_event(JsonEvent::ObjectKey("foo".into()), &mut output)?;
+    /// writer.serialize_raw_value(b"[1, 2]", &mut output)?;
+    /// writer.serialize_event(JsonEvent::ObjectKey("bar".into()), &mut output)?;
+    /// assert!(writer.serialize_raw_value(b"[1, 2", &mut output).is_err());
+    /// writer.serialize_raw_value(b"null", &mut output)?;
+    /// writer.serialize_event(JsonEvent::EndObject, &mut output)?;
+    ///
+    /// assert_eq!(output.as_slice(), b"{\"foo\":[1, 2],\"bar\":null}");
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn serialize_raw_value(&mut self, value: &[u8], write: impl Write) -> Result<()> {
+        if value.starts_with(&[0xEF, 0xBB, 0xBF]) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "A raw JSON value is not allowed to start with a byte order mark",
+            ));
+        }
+        let mut parser = SliceJsonParser::new(value);
+        if self.i_json_object_keys.is_some() {
+            parser = parser.with_i_json_validation();
+        }
+        loop {
+            match parser.parse_next() {
+                Ok(JsonEvent::Eof) => break,
+                Ok(_) => (),
+                Err(e) => return Err(Error::new(ErrorKind::InvalidInput, e)),
+            }
+        }
+        self.serialize_unvalidated_raw_value(value, write)
+    }
+
+    /// Writes an already serialized JSON value as it is without checking it is valid JSON.
+    ///
+    /// It is the responsibility of the caller to make sure `value` is a single well-formed JSON value,
+    /// the output is going to be invalid otherwise.
+    pub fn serialize_unvalidated_raw_value(
+        &mut self,
+        value: &[u8],
+        mut write: impl Write,
+    ) -> Result<()> {
+        self.before_value(&mut write)?;
+        write.write_all(value)
+    }
+
+    /// Starts a string value whose content is then written in chunks.
+    ///
+    /// The content is given with [`serialize_string_chunk`](Self::serialize_string_chunk)
+    /// and the string is closed with [`end_string`](Self::end_string).
+    /// It allows to write very long strings without having them in memory.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, LowLevelJsonSerializer};
+    ///
+    /// let mut writer = LowLevelJsonSerializer::new();
+    /// let mut output = Vec::new();
+    /// writer.serialize_event(JsonEvent::StartObject, &mut output)?;
+    /// writer.start_object_key(&mut output)?;
+    /// writer.serialize_string_chunk("fo", &mut output)?;
+    /// writer.serialize_string_chunk("o", &mut output)?;
+    /// writer.end_object_key(&mut output)?;
+    /// writer.start_string(&mut output)?;
+    /// writer.serialize_string_chunk("line 1\n", &mut output)?;
+    /// writer.serialize_string_chunk("line 2\n", &mut output)?;
+    /// writer.end_string(&mut output)?;
+    /// writer.serialize_event(JsonEvent::EndObject, &mut output)?;
+    ///
+    /// assert_eq!(output.as_slice(), b"{\"foo\":\"line 1\\nline 2\\n\"}");
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn start_string(&mut self, mut write: impl Write) -> Result<()> {
+        self.before_value(&mut write)?;
+        self.state_stack.push(JsonState::InString);
+        write.write_all(b"\"")
+    }
+
+    /// Writes a chunk of the string or of the object key currently written.
+    ///
+    /// The chunk is escaped.
+    pub fn serialize_string_chunk(&mut self, chunk: &str, write: impl Write) -> Result<()> {
+        match self.state_stack.last() {
+            Some(JsonState::InString) => {
+                if self.i_json_object_keys.is_some() {
+                    check_i_json_event(&JsonEvent::StringChunk(Cow::Borrowed(chunk)))
+                        .map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
+                }
+                write_escaped_json_string_content(chunk, write)
+            }
+            Some(JsonState::InObjectKey) => {
+                if self.i_json_object_keys.is_some() {
+                    let event = JsonEvent::ObjectKeyChunk(Cow::Borrowed(chunk));
+                    check_i_json_event(&event)
+                        .map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
+                    self.track_i_json_object_key_event(&event)?;
+                }
+                write_escaped_json_string_content(chunk, write)
+            }
+            _ => Err(Error::new(
+                ErrorKind::InvalidInput,
+                "No string or object key has been started",
+            )),
+        }
+    }
+
+    /// Ends a string value started with [`start_string`](Self::start_string).
+    pub fn end_string(&mut self, mut write: impl Write) -> Result<()> {
+        if !matches!(self.state_stack.last(), Some(JsonState::InString)) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "No string value has been started",
+            ));
+        }
+        self.state_stack.pop();
+        write.write_all(b"\"")
+    }
+
+    /// Starts an object key whose content is then written in chunks.
+    ///
+    /// The content is given with [`serialize_string_chunk`](Self::serialize_string_chunk)
+    /// and the key is closed with [`end_object_key`](Self::end_object_key).
+    pub fn start_object_key(&mut self, mut write: impl Write) -> Result<()> {
+        self.before_object_key(&mut write)?;
+        self.state_stack.push(JsonState::InObjectKey);
+        write.write_all(b"\"")
+    }
+
+    /// Ends an object key started with [`start_object_key`](Self::start_object_key).
+    pub fn end_object_key(&mut self, mut write: impl Write) -> Result<()> {
+        if !matches!(self.state_stack.last(), Some(JsonState::InObjectKey)) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "No object key has been started",
+            ));
+        }
+        self.track_i_json_object_key_event(&JsonEvent::ObjectKey("".into()))?;
+        self.state_stack.pop();
+        write.write_all(b"\":")
+    }
+
+    /// Starts a string value whose content is raw bytes encoded in [base64](https://www.rfc-editor.org/rfc/rfc4648#section-4) on the fly.
+    ///
+    /// The bytes are given with [`serialize_base64_chunk`](Self::serialize_base64_chunk)
+    /// and the string is closed with [`end_base64_string`](Self::end_base64_string).
+    /// The chunks do not need to be aligned on groups of 3 bytes.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, LowLevelJsonSerializer};
+    ///
+    /// let mut writer = LowLevelJsonSerializer::new();
+    /// let mut output = Vec::new();
+    /// writer.serialize_event(JsonEvent::StartArray, &mut output)?;
+    /// writer.start_base64_string(&mut output)?;
+    /// writer.serialize_base64_chunk(b"he", &mut output)?;
+    /// writer.serialize_base64_chunk(b"llo", &mut output)?;
+    /// writer.end_base64_string(&mut output)?;
+    /// writer.serialize_event(JsonEvent::EndArray, &mut output)?;
+    ///
+    /// assert_eq!(output.as_slice(), b"[\"aGVsbG8=\"]");
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn start_base64_string(&mut self, mut write: impl Write) -> Result<()> {
+        self.before_value(&mut write)?;
+        self.state_stack.push(JsonState::InBase64String);
+        write.write_all(b"\"")
+    }
+
+    /// Encodes in base64 and writes a chunk of the bytes of the string currently written.
+    pub fn serialize_base64_chunk(&mut self, chunk: &[u8], write: impl Write) -> Result<()> {
+        if !matches!(self.state_stack.last(), Some(JsonState::InBase64String)) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "No base64 string has been started",
+            ));
+        }
+        self.base64_encoder.encode_chunk(chunk, write)
+    }
+
+    /// Ends a string started with [`start_base64_string`](Self::start_base64_string).
+    ///
+    /// The remaining bytes are encoded with padding.
+    pub fn end_base64_string(&mut self, mut write: impl Write) -> Result<()> {
+        if !matches!(self.state_stack.last(), Some(JsonState::InBase64String)) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "No base64 string has been started",
+            ));
+        }
+        self.state_stack.pop();
+        self.base64_encoder.finish(&mut write)?;
+        write.write_all(b"\"")
+    }
+
+    /// Writes bytes as a base64 encoded string value.
+    ///
+    /// Use [`start_base64_string`](Self::start_base64_string) to write bytes that are not all in memory.
+    ///
+    /// ```
+    /// use json_event_parser::{JsonEvent, LowLevelJsonSerializer};
+    ///
+    /// let mut writer = LowLevelJsonSerializer::new();
+    /// let mut output = Vec::new();
+    /// writer.serialize_event(JsonEvent::StartObject, &mut output)?;
+    /// writer.serialize_event(JsonEvent::ObjectKey("data".into()), &mut output)?;
+    /// writer.serialize_base64(b"hello", &mut output)?;
+    /// writer.serialize_event(JsonEvent::EndObject, &mut output)?;
+    ///
+    /// assert_eq!(output.as_slice(), b"{\"data\":\"aGVsbG8=\"}");
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn serialize_base64(&mut self, value: &[u8], mut write: impl Write) -> Result<()> {
+        self.start_base64_string(&mut write)?;
+        self.serialize_base64_chunk(value, &mut write)?;
+        self.end_base64_string(write)
+    }
+
+    fn before_object_key(&mut self, mut write: impl Write) -> Result<()> {
+        match self.state_stack.pop() {
+            Some(JsonState::OpenObject) => (),
+            Some(JsonState::ContinuationObject) => write.write_all(b",")?,
+            Some(s) => {
+                self.state_stack.push(s);
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "Trying to write an object key in an not object",
+                ));
+            }
+            None => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "Trying to write an object key in an not object",
+                ))
+            }
+        }
+        self.state_stack.push(JsonState::ContinuationObject);
+        self.state_stack.push(JsonState::ObjectValue);
+        Ok(())
+    }
+
     fn before_value(&mut self, mut write: impl Write) -> Result<()> {
         match self.state_stack.pop() {
             Some(JsonState::OpenArray) => {
@@ -240,6 +1115,15 @@ impl LowLevelJsonSerializer {
                 ))
             }
             Some(JsonState::ObjectValue) => Ok(()),
+            Some(
+                state @ (JsonState::InString | JsonState::InObjectKey | JsonState::InBase64String),
+            ) => {
+                self.state_stack.push(state);
+                Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "A string is being written, it must be ended first",
+                ))
+            }
             None => {
                 if self.element_written {
                     Err(Error::new(
@@ -277,10 +1161,22 @@ enum JsonState {
     OpenObject,
     ContinuationObject,
     ObjectValue,
+    /// A string value has been started with a chunk
+    InString,
+    /// An object key has been started with a chunk
+    InObjectKey,
+    /// A string value containing base64 encoded bytes has been started
+    InBase64String,
 }
 
-fn write_escaped_json_string(s: &str, mut write: impl Write) -> Result<()> {
+pub(crate) fn write_escaped_json_string(s: &str, mut write: impl Write) -> Result<()> {
     write.write_all(b"\"")?;
+    write_escaped_json_string_content(s, &mut write)?;
+    write.write_all(b"\"")
+}
+
+/// Writes the escaped string without the surrounding quotes.
+pub(crate) fn write_escaped_json_string_content(s: &str, mut write: impl Write) -> Result<()> {
     let mut buffer = [b'\\', b'u', 0, 0, 0, 0];
     for c in s.chars() {
         match c {
@@ -310,7 +1206,6 @@ fn write_escaped_json_string(s: &str, mut write: impl Write) -> Result<()> {
             }
         }?;
     }
-    write.write_all(b"\"")?;
     Ok(())
 }
 
@@ -342,4 +1237,36 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn raw_value_serialization() -> Result<()> {
+        let mut serializer = WriterJsonSerializer::new(Vec::new());
+        serializer.serialize_event(JsonEvent::StartArray)?;
+        serializer.serialize_raw_value(b" {\"a\": [1, {}]}\n")?;
+        serializer.serialize_raw_value(b"\"b\"")?;
+        serializer.serialize_event(JsonEvent::StartObject)?;
+        assert!(serializer.serialize_raw_value(b"1").is_err());
+        serializer.serialize_event(JsonEvent::ObjectKey("c".into()))?;
+        serializer.serialize_raw_value(b"2")?;
+        serializer.serialize_event(JsonEvent::EndObject)?;
+        for invalid in [
+            b"".as_slice(),
+            b"1 2",
+            b"[1,]",
+            b"{\"a\"}",
+            b"\xEF\xBB\xBF1",
+        ] {
+            assert!(
+                serializer.serialize_raw_value(invalid).is_err(),
+                "{}",
+                String::from_utf8_lossy(invalid)
+            );
+        }
+        serializer.serialize_event(JsonEvent::EndArray)?;
+        assert_eq!(
+            serializer.finish()?,
+            b"[ {\"a\": [1, {}]}\n,\"b\",{\"c\":2}]"
+        );
+        Ok(())
+    }
 }
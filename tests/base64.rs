@@ -0,0 +1,91 @@
+use json_event_parser::{
+    decode_base64, Base64Alphabet, Base64Decoder, JsonEvent, SliceJsonParser, WriterJsonSerializer,
+};
+
+#[test]
+fn test_base64_decoding() {
+    for (input, expected) in [
+        ("", b"".as_slice()),
+        ("Zg==", b"f"),
+        ("Zg", b"f"),
+        ("Zm8=", b"fo"),
+        ("Zm8", b"fo"),
+        ("Zm9v", b"foo"),
+        ("Zm9vYg==", b"foob"),
+        ("Zm9vYmE=", b"fooba"),
+        ("Zm9vYmFy", b"foobar"),
+        ("+/+/", b"\xFB\xFF\xBF"),
+    ] {
+        assert_eq!(
+            decode_base64(input, Base64Alphabet::Standard).unwrap(),
+            expected,
+            "{input}"
+        );
+        let url_safe_input = input.replace('+', "-").replace('/', "_");
+        assert_eq!(
+            decode_base64(&url_safe_input, Base64Alphabet::UrlSafe).unwrap(),
+            expected,
+            "{url_safe_input}"
+        );
+    }
+}
+
+#[test]
+fn test_base64_decoding_errors() {
+    for (input, alphabet, location) in [
+        ("Zg=a", Base64Alphabet::Standard, 3..4),
+        ("Zg=", Base64Alphabet::Standard, 3..3),
+        ("Zm8==", Base64Alphabet::Standard, 4..5),
+        ("Z===", Base64Alphabet::Standard, 1..2),
+        ("=", Base64Alphabet::Standard, 0..1),
+        ("Zm9vY", Base64Alphabet::Standard, 4..5),
+        ("Zm9v Yg", Base64Alphabet::Standard, 4..5),
+        ("Zm9vé", Base64Alphabet::Standard, 4..6),
+        ("-_", Base64Alphabet::Standard, 0..1),
+        ("+/", Base64Alphabet::UrlSafe, 0..1),
+    ] {
+        let error = decode_base64(input, alphabet).unwrap_err();
+        assert_eq!(error.location(), location, "{input}: {error}");
+    }
+}
+
+#[test]
+fn test_base64_roundtrip() {
+    let input = (0..=255).cycle().take(1000).collect::<Vec<u8>>();
+    for alphabet in [Base64Alphabet::Standard, Base64Alphabet::UrlSafe] {
+        for padding in [true, false] {
+            for len in [0, 1, 2, 3, 999, 1000] {
+                let mut writer = WriterJsonSerializer::new(Vec::new())
+                    .with_base64_alphabet(alphabet)
+                    .with_base64_padding(padding);
+                writer.serialize_base64(&input[..len]).unwrap();
+                let json = writer.finish().unwrap();
+                assert_eq!(json.contains(&b'='), padding && len % 3 != 0);
+
+                let mut parser = SliceJsonParser::new(&json);
+                let JsonEvent::String(value) = parser.parse_next().unwrap() else {
+                    panic!("A string is expected")
+                };
+                assert_eq!(decode_base64(&value, alphabet).unwrap(), &input[..len]);
+
+                let mut parser = SliceJsonParser::new(&json).with_string_chunk_size(7);
+                let mut decoder = Base64Decoder::new(alphabet);
+                let mut output = Vec::new();
+                loop {
+                    match parser.parse_next().unwrap() {
+                        JsonEvent::StringChunk(chunk) => {
+                            decoder.decode_chunk(&chunk, &mut output).unwrap()
+                        }
+                        JsonEvent::String(end) => {
+                            decoder.decode_chunk(&end, &mut output).unwrap();
+                            break;
+                        }
+                        _ => panic!("A string is expected"),
+                    }
+                }
+                decoder.finish(&mut output).unwrap();
+                assert_eq!(output, &input[..len]);
+            }
+        }
+    }
+}
@@ -0,0 +1,196 @@
+#[cfg(feature = "async-tokio")]
+use json_event_parser::{tokio_async_pipe_events, TokioAsyncBufReaderJsonParser};
+use json_event_parser::{BufReaderJsonParser, JsonEvent, JsonLimits, SliceJsonParser};
+use std::io::{self, BufRead, BufReader, Read};
+
+const JSON: &str = "\u{FEFF}{\"key\": [1, \"long string\", {\"a\": true, \"b\": null}],\n \"k2\": \"é😀\\u00e9\", \"k3\": -1.5e3, \"k4\": [[], {}, false]}";
+
+fn parse_slice(input: &[u8]) -> Vec<Result<JsonEvent<'static>, String>> {
+    let mut parser = SliceJsonParser::new(input);
+    let mut events = Vec::new();
+    loop {
+        match parser.parse_next() {
+            Ok(JsonEvent::Eof) => return events,
+            Ok(event) => events.push(Ok(event.into_owned())),
+            Err(error) => events.push(Err(error.to_string())),
+        }
+    }
+}
+
+fn parse_buf_read(
+    mut parser: BufReaderJsonParser<impl BufRead>,
+) -> Vec<Result<JsonEvent<'static>, String>> {
+    let mut events = Vec::new();
+    loop {
+        match parser.parse_next() {
+            Ok(JsonEvent::Eof) => return events,
+            Ok(event) => events.push(Ok(event.into_owned())),
+            Err(error) => events.push(Err(error.to_string())),
+        }
+    }
+}
+
+#[test]
+fn test_buf_read_with_small_buffers() {
+    for input in [JSON, "{\"a\": 1 \"b\"}", "[1, 2, ", "123", "  \"a\" \n"] {
+        let expected = parse_slice(input.as_bytes());
+        for capacity in 1..20 {
+            assert_eq!(
+                parse_buf_read(BufReaderJsonParser::new(BufReader::with_capacity(
+                    capacity,
+                    input.as_bytes()
+                ))),
+                expected,
+                "{input} with a capacity of {capacity}"
+            );
+        }
+        assert_eq!(
+            parse_buf_read(BufReaderJsonParser::new(input.as_bytes())),
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_buf_read_peek() {
+    let mut parser = BufReaderJsonParser::new(BufReader::with_capacity(3, JSON.as_bytes()));
+    let mut expected = parse_slice(JSON.as_bytes()).into_iter();
+    loop {
+        let peeked = parser.peek_next().unwrap().clone();
+        assert_eq!(parser.peek_next().unwrap(), &peeked);
+        let event = parser.parse_next().unwrap();
+        assert_eq!(event, peeked);
+        if event == JsonEvent::Eof {
+            break;
+        }
+        assert_eq!(Ok(event.into_owned()), expected.next().unwrap());
+    }
+    assert!(expected.next().is_none());
+}
+
+#[test]
+fn test_buf_read_string_chunks_and_max_buffer_size() {
+    let json = format!("[\"{}\"]", "a".repeat(10_000));
+    let mut parser = BufReaderJsonParser::new(BufReader::with_capacity(100, json.as_bytes()))
+        .with_max_buffer_size(256)
+        .with_string_chunk_size(64);
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::StartArray);
+    let mut string = String::new();
+    loop {
+        match parser.parse_next().unwrap() {
+            JsonEvent::StringChunk(chunk) => string.push_str(&chunk),
+            JsonEvent::String(end) => {
+                string.push_str(&end);
+                break;
+            }
+            _ => unreachable!(),
+        }
+    }
+    assert_eq!(string.len(), 10_000);
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::EndArray);
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::Eof);
+    assert_eq!(parser.position().offset, 10_004);
+
+    let mut parser = BufReaderJsonParser::new(BufReader::with_capacity(100, json.as_bytes()))
+        .with_max_buffer_size(256);
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::StartArray);
+    parser.parse_next().unwrap_err();
+}
+
+#[test]
+fn test_buf_read_limits_and_reset() {
+    let mut parser = BufReaderJsonParser::new(BufReader::with_capacity(2, b"[1, 2, 3]".as_slice()))
+        .with_limits(JsonLimits::new().with_max_array_elements(2));
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::StartArray);
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::Number("1".into()));
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::Number("2".into()));
+    parser.parse_next().unwrap_err();
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::Eof);
+
+    parser.reset_with_reader(BufReader::with_capacity(2, b"[12, 3]".as_slice()));
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::StartArray);
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::Number("12".into()));
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::Number("3".into()));
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::EndArray);
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::Eof);
+}
+
+#[test]
+fn test_buf_read_consumes_only_parsed_bytes() {
+    let mut parser = BufReaderJsonParser::new(b"[1] ".as_slice());
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::StartArray);
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::Number("1".into()));
+    assert_eq!(parser.reset_with_reader(b"".as_slice()), b"] ");
+}
+
+/// A [`BufRead`] moving its buffer to a new allocation on each call to `consume`
+struct ReallocatingBufRead<'a> {
+    input: &'a [u8],
+    buffer: Vec<u8>,
+}
+
+impl Read for ReallocatingBufRead<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.fill_buf()?.read(buf)?;
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl BufRead for ReallocatingBufRead<'_> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.buffer.is_empty() {
+            let len = self.input.len().min(8);
+            self.buffer.extend_from_slice(&self.input[..len]);
+            self.input = &self.input[len..];
+        }
+        Ok(&self.buffer)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let new_buffer = self.buffer[amt..].to_vec();
+        let mut old_buffer = std::mem::replace(&mut self.buffer, new_buffer);
+        old_buffer.fill(0);
+    }
+}
+
+#[test]
+fn test_buf_read_with_reallocating_consume() {
+    let input = JSON.as_bytes();
+    assert_eq!(
+        parse_buf_read(BufReaderJsonParser::new(ReallocatingBufRead {
+            input,
+            buffer: Vec::new(),
+        })),
+        parse_slice(input)
+    );
+}
+
+#[cfg(feature = "async-tokio")]
+#[tokio::test]
+async fn test_tokio_async_buf_read() {
+    let expected = parse_slice(JSON.as_bytes());
+    for capacity in 1..20 {
+        let mut parser = TokioAsyncBufReaderJsonParser::new(tokio::io::BufReader::with_capacity(
+            capacity,
+            JSON.as_bytes(),
+        ));
+        let mut events = Vec::new();
+        loop {
+            match parser.parse_next().await.unwrap() {
+                JsonEvent::Eof => break,
+                event => events.push(Ok(event.into_owned())),
+            }
+        }
+        assert_eq!(events, expected, "capacity of {capacity}");
+    }
+
+    let mut events = Vec::new();
+    tokio_async_pipe_events(
+        TokioAsyncBufReaderJsonParser::new(JSON.as_bytes()),
+        &mut events,
+    )
+    .await
+    .unwrap();
+    assert_eq!(events.into_iter().map(Ok).collect::<Vec<_>>(), expected);
+}
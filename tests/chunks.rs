@@ -0,0 +1,170 @@
+use json_event_parser::{
+    JsonEvent, JsonPath, JsonValue, ReaderJsonParser, SliceJsonParser, WriterJsonSerializer,
+};
+
+const JSON: &str = r#"{"key\n\"long\" é😀": ["a\\b\/cé😀 end", "", "short", "ééééé"], "k": "𝄞𝄞𝄞"}"#;
+
+fn reserialize(parser: &mut SliceJsonParser<'_>) -> (String, usize) {
+    let mut writer = WriterJsonSerializer::new(Vec::new());
+    let mut chunk_count = 0;
+    loop {
+        match parser.parse_next().unwrap() {
+            JsonEvent::Eof => break,
+            event => {
+                if matches!(
+                    event,
+                    JsonEvent::StringChunk(_) | JsonEvent::ObjectKeyChunk(_)
+                ) {
+                    chunk_count += 1;
+                }
+                writer.serialize_event(event).unwrap();
+            }
+        }
+    }
+    (
+        String::from_utf8(writer.finish().unwrap()).unwrap(),
+        chunk_count,
+    )
+}
+
+#[test]
+fn test_chunked_strings() {
+    let (expected, chunk_count) = reserialize(&mut SliceJsonParser::new(JSON.as_bytes()));
+    assert_eq!(chunk_count, 0);
+    let expected_value = JSON.parse::<JsonValue>().unwrap();
+    for chunk_size in 1..16 {
+        let (actual, chunk_count) = reserialize(
+            &mut SliceJsonParser::new(JSON.as_bytes()).with_string_chunk_size(chunk_size),
+        );
+        assert_eq!(actual, expected, "with chunks of {chunk_size} bytes");
+        assert!(chunk_count > 0);
+
+        let value = JsonValue::from_events(
+            ReaderJsonParser::new(JSON.as_bytes()).with_string_chunk_size(chunk_size),
+        )
+        .unwrap();
+        assert_eq!(value, expected_value);
+
+        let selected = JsonPath::parse("$['key\\n\"long\" é😀'][0]")
+            .unwrap()
+            .select(SliceJsonParser::new(JSON.as_bytes()).with_string_chunk_size(chunk_size))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(selected.len(), 1);
+    }
+}
+
+#[test]
+fn test_chunked_string_errors() {
+    for input in [
+        b"\"aaaaaaaa\\x\"".as_slice(),
+        b"\"aaaaaaaa\xFF\"",
+        b"\"aaaaaaaa",
+        b"[\"aaaaaaaa\"\"aaaaaaaa\"]",
+    ] {
+        let mut parser = SliceJsonParser::new(input).with_string_chunk_size(2);
+        let mut has_error = false;
+        loop {
+            match parser.parse_next() {
+                Ok(JsonEvent::Eof) => break,
+                Ok(_) => (),
+                Err(_) => has_error = true,
+            }
+        }
+        assert!(has_error, "{}", String::from_utf8_lossy(input));
+    }
+}
+
+#[test]
+fn test_streamed_strings() {
+    let mut writer = WriterJsonSerializer::new(Vec::new());
+    writer.serialize_event(JsonEvent::StartObject).unwrap();
+    writer.start_object_key().unwrap();
+    for chunk in ["key\n", "\"long\" ", "é😀"] {
+        writer.serialize_string_chunk(chunk).unwrap();
+    }
+    writer.end_object_key().unwrap();
+    writer.serialize_event(JsonEvent::StartArray).unwrap();
+    writer.start_string().unwrap();
+    writer.serialize_string_chunk("a\\b/cé").unwrap();
+    writer.serialize_string_chunk("😀 end").unwrap();
+    writer.end_string().unwrap();
+    writer.start_string().unwrap();
+    writer.end_string().unwrap();
+    writer.end_string().unwrap_err();
+    writer.serialize_event(JsonEvent::EndArray).unwrap();
+    writer.serialize_string_chunk("a").unwrap_err();
+    writer.serialize_event(JsonEvent::EndObject).unwrap();
+    let output = writer.finish().unwrap();
+    assert_eq!(
+        output,
+        r#"{"key\n\"long\" é😀":["a\\b/cé😀 end",""]}"#.as_bytes()
+    );
+}
+
+#[test]
+fn test_streamed_base64() {
+    for (input, expected) in [
+        (b"".as_slice(), ""),
+        (b"f", "Zg=="),
+        (b"fo", "Zm8="),
+        (b"foo", "Zm9v"),
+        (b"foob", "Zm9vYg=="),
+        (b"fooba", "Zm9vYmE="),
+        (b"foobar", "Zm9vYmFy"),
+        (b"\xFB\xFF\xBF", "+/+/"),
+    ] {
+        for chunk_size in 1..4 {
+            let mut writer = WriterJsonSerializer::new(Vec::new());
+            writer.start_base64_string().unwrap();
+            for chunk in input.chunks(chunk_size) {
+                writer.serialize_base64_chunk(chunk).unwrap();
+            }
+            writer.end_base64_string().unwrap();
+            assert_eq!(
+                String::from_utf8(writer.finish().unwrap()).unwrap(),
+                format!("\"{expected}\"")
+            );
+        }
+    }
+
+    let input = (0..=255).cycle().take(5000).collect::<Vec<u8>>();
+    let mut expected = WriterJsonSerializer::new(Vec::new());
+    expected.start_base64_string().unwrap();
+    expected.serialize_base64_chunk(&input).unwrap();
+    expected.end_base64_string().unwrap();
+    let expected = expected.finish().unwrap();
+    for chunk_size in [1, 2, 5, 1000, 2048] {
+        let mut writer = WriterJsonSerializer::new(Vec::new());
+        writer.start_base64_string().unwrap();
+        for chunk in input.chunks(chunk_size) {
+            writer.serialize_base64_chunk(chunk).unwrap();
+        }
+        writer.end_base64_string().unwrap();
+        assert_eq!(writer.finish().unwrap(), expected);
+    }
+}
+
+#[test]
+fn test_streamed_string_errors() {
+    let mut writer = WriterJsonSerializer::new(Vec::new());
+    writer.start_object_key().unwrap_err();
+    writer.serialize_base64_chunk(b"a").unwrap_err();
+    writer.start_base64_string().unwrap();
+    writer.serialize_string_chunk("a").unwrap_err();
+    writer.end_string().unwrap_err();
+    writer.serialize_event(JsonEvent::Null).unwrap_err();
+    writer.start_string().unwrap_err();
+    writer.end_base64_string().unwrap();
+    writer.finish().unwrap();
+
+    let mut writer = WriterJsonSerializer::new(Vec::new());
+    writer.serialize_event(JsonEvent::StartObject).unwrap();
+    writer.start_string().unwrap_err();
+    writer.start_object_key().unwrap();
+    writer.end_string().unwrap_err();
+    writer.serialize_event(JsonEvent::EndObject).unwrap_err();
+    writer.end_object_key().unwrap();
+    writer.start_string().unwrap();
+    writer.finish().unwrap_err();
+}
@@ -0,0 +1,83 @@
+use json_event_parser::{JsonDiagnosticRenderer, JsonSyntaxError, SliceJsonParser};
+
+fn first_error(source: &[u8]) -> JsonSyntaxError {
+    let mut parser = SliceJsonParser::new(source);
+    loop {
+        if let Err(error) = parser.parse_next() {
+            return error;
+        }
+    }
+}
+
+#[test]
+fn test_render_single_line() {
+    let source = b"[1, 2 3]";
+    assert_eq!(
+        JsonDiagnosticRenderer::new(source).render(&first_error(source)),
+        "error: Array values must be followed by a comma to add a new value or a squared bracket to end the array
+ --> 1:7
+  |
+1 | [1, 2 3]
+  |       ^
+"
+    );
+}
+
+#[test]
+fn test_render_tabs_and_unicode() {
+    let source = "{\"é\":\t\t\"😀\" nul}".as_bytes();
+    assert_eq!(
+        JsonDiagnosticRenderer::new(source)
+            .with_tab_width(2)
+            .render(&first_error(source)),
+        "error: null expected
+ --> 1:14
+  |
+1 | {\"é\":    \"😀\" nul}
+  |              ^^^
+"
+    );
+}
+
+#[test]
+fn test_render_end_of_file() {
+    let source = b"{\"a\": 1\r\n";
+    assert_eq!(
+        JsonDiagnosticRenderer::new(source)
+            .with_file_name("a.json")
+            .render(&first_error(source)),
+        "error: Object values must be followed by a comma to add a new value or a curly bracket to end the object
+ --> a.json:2:1
+  |
+2 | 
+  | ^
+"
+    );
+}
+
+#[test]
+fn test_render_line_number() {
+    let source = b"[\n\n\"a\nb\nc\nd\"]";
+    let error = first_error(source);
+    assert_eq!(error.location().start.line, 2);
+    assert_eq!(
+        JsonDiagnosticRenderer::new(source).render(&error),
+        "error: '\n' is not allowed in JSON strings
+ --> 3:3
+  |
+3 | \"a
+  |   ^
+"
+    );
+}
+
+#[test]
+fn test_render_json() {
+    let source = b"[1,\n]";
+    assert_eq!(
+        JsonDiagnosticRenderer::new(source)
+            .with_file_name("a \"b\".json")
+            .render_json(&first_error(source)),
+        r#"{"severity":"error","message":"Trailing commas are not allowed","file":"a \"b\".json","start":{"line":1,"column":0,"offset":4},"end":{"line":1,"column":1,"offset":5}}"#
+    );
+}
@@ -0,0 +1,75 @@
+use json_event_parser::{JsonEvent, JsonSyntaxError, SliceJsonParser};
+
+fn parse(parser: SliceJsonParser<'_>) -> (Vec<JsonEvent<'_>>, Vec<JsonSyntaxError>) {
+    let mut events = Vec::new();
+    let mut errors = Vec::new();
+    let mut parser = parser;
+    loop {
+        match parser.parse_next() {
+            Ok(JsonEvent::Eof) => break,
+            Ok(event) => events.push(event),
+            Err(e) => errors.push(e),
+        }
+    }
+    (events, errors)
+}
+
+#[test]
+fn test_duplicate_keys() {
+    for (json, duplicate_offsets) in [
+        (r#"{"a": 1, "b": 2}"#.as_bytes(), Vec::<u64>::new()),
+        (br#"{"a": 1, "a": 2}"#, vec![9]),
+        (br#"{"a": {"a": 1}, "b": [{"a": 1}, {"a": 2}]}"#, vec![]),
+        (br#"{"a": {"b": 1, "b": 2}, "a": 3}"#, vec![15, 24]),
+        (br#"[{"a": 1}, {"b": 1, "a": 2, "b": 3}]"#, vec![28]),
+    ] {
+        let (events, errors) = parse(SliceJsonParser::new(json));
+        assert!(errors.is_empty());
+        let (events_with_check, errors) =
+            parse(SliceJsonParser::new(json).with_duplicate_object_key_detection());
+        assert_eq!(events, events_with_check);
+        assert_eq!(
+            errors
+                .iter()
+                .map(|e| e.location().start.offset)
+                .collect::<Vec<_>>(),
+            duplicate_offsets,
+            "{}",
+            String::from_utf8_lossy(json)
+        );
+
+        let (events_with_chunks, errors) = parse(
+            SliceJsonParser::new(json)
+                .with_string_chunk_size(1)
+                .with_duplicate_object_key_detection(),
+        );
+        assert!(events_with_chunks.len() >= events.len());
+        assert_eq!(
+            errors
+                .iter()
+                .map(|e| e.location().start.offset)
+                .collect::<Vec<_>>(),
+            duplicate_offsets,
+            "{}",
+            String::from_utf8_lossy(json)
+        );
+    }
+}
+
+#[test]
+fn test_tracked_object_keys_size() {
+    let json = br#"{"aa": {"bb": 1}, "cc": 2}"#;
+    let (_, errors) = parse(SliceJsonParser::new(json).with_max_tracked_object_keys_size(6));
+    assert!(errors.is_empty());
+    // The keys of the nested object are released when it ends
+    let (_, errors) = parse(SliceJsonParser::new(json).with_max_tracked_object_keys_size(4));
+    assert!(errors.is_empty());
+    let (_, errors) = parse(SliceJsonParser::new(json).with_max_tracked_object_keys_size(3));
+    assert_eq!(errors.len(), 2);
+    let (_, errors) = parse(
+        SliceJsonParser::new(json)
+            .with_string_chunk_size(1)
+            .with_max_tracked_object_keys_size(3),
+    );
+    assert_eq!(errors.len(), 2);
+}
@@ -0,0 +1,185 @@
+use json_event_parser::{JsonEvent, JsonParseError, JsonValue, ReaderJsonParser, TextPosition};
+use std::io::{self, Read};
+
+const JSON: &str = "{\"key é\": [\"a\\u00e9😀𝄞\", 1.5, true, null], \"k\": \"ﬁ\"}";
+
+/// Returns at most one byte per read
+struct OneByteReader<'a>(&'a [u8]);
+
+impl Read for OneByteReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let Some((first, rest)) = self.0.split_first() else {
+            return Ok(0);
+        };
+        let Some(output) = buf.first_mut() else {
+            return Ok(0);
+        };
+        *output = *first;
+        self.0 = rest;
+        Ok(1)
+    }
+}
+
+fn encode(input: &str, bom: bool, encode_char: impl Fn(char) -> Vec<u8>) -> Vec<u8> {
+    let mut output = Vec::new();
+    if bom {
+        output.extend(encode_char('\u{FEFF}'));
+    }
+    for c in input.chars() {
+        output.extend(encode_char(c));
+    }
+    output
+}
+
+fn utf16(input: &str, bom: bool, little_endian: bool) -> Vec<u8> {
+    encode(input, bom, |c| {
+        c.encode_utf16(&mut [0; 2])
+            .iter()
+            .flat_map(|u| {
+                if little_endian {
+                    u.to_le_bytes()
+                } else {
+                    u.to_be_bytes()
+                }
+            })
+            .collect()
+    })
+}
+
+fn utf32(input: &str, bom: bool, little_endian: bool) -> Vec<u8> {
+    encode(input, bom, |c| {
+        if little_endian {
+            u32::from(c).to_le_bytes().to_vec()
+        } else {
+            u32::from(c).to_be_bytes().to_vec()
+        }
+    })
+}
+
+fn all_encodings(input: &str) -> Vec<Vec<u8>> {
+    let mut encoded = vec![
+        input.as_bytes().to_vec(),
+        encode(input, true, |c| c.to_string().into_bytes()),
+    ];
+    for bom in [false, true] {
+        for little_endian in [false, true] {
+            encoded.push(utf16(input, bom, little_endian));
+            encoded.push(utf32(input, bom, little_endian));
+        }
+    }
+    encoded
+}
+
+#[test]
+fn test_encoding_detection() {
+    let expected = JSON.parse::<JsonValue>().unwrap();
+    for input in all_encodings(JSON) {
+        let value = JsonValue::from_events(
+            ReaderJsonParser::new(input.as_slice()).with_encoding_detection(),
+        )
+        .unwrap();
+        assert_eq!(value, expected, "{input:?}");
+        let value = JsonValue::from_events(
+            ReaderJsonParser::new(OneByteReader(&input))
+                .with_encoding_detection()
+                .with_string_chunk_size(1),
+        )
+        .unwrap();
+        assert_eq!(value, expected, "{input:?}");
+    }
+}
+
+#[test]
+fn test_encoding_detection_short_documents() {
+    for json in ["1", "\"\"", "[]"] {
+        for input in all_encodings(json) {
+            let value = JsonValue::from_events(
+                ReaderJsonParser::new(input.as_slice()).with_encoding_detection(),
+            )
+            .unwrap();
+            assert_eq!(value, json.parse::<JsonValue>().unwrap(), "{input:?}");
+        }
+    }
+}
+
+#[test]
+fn test_encoding_detection_error_location() {
+    let input = utf16("[\"é😀\",\n \"ab\" x]", false, true);
+    let mut parser = ReaderJsonParser::new(input.as_slice()).with_encoding_detection();
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::StartArray);
+    assert_eq!(
+        parser.parse_next().unwrap(),
+        JsonEvent::String("é😀".into())
+    );
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::String("ab".into()));
+    let JsonParseError::Syntax(error) = parser.parse_next().unwrap_err() else {
+        unreachable!()
+    };
+    assert_eq!(
+        error.location(),
+        TextPosition {
+            line: 1,
+            column: 12,
+            offset: 28
+        }..TextPosition {
+            line: 1,
+            column: 14,
+            offset: 30
+        }
+    );
+
+    let input = utf32("[\"é😀\", x]", true, false);
+    let mut parser = ReaderJsonParser::new(input.as_slice()).with_encoding_detection();
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::StartArray);
+    assert_eq!(
+        parser.parse_next().unwrap(),
+        JsonEvent::String("é😀".into())
+    );
+    let JsonParseError::Syntax(error) = parser.parse_next().unwrap_err() else {
+        unreachable!()
+    };
+    assert_eq!(error.location().start.column, 32);
+    assert_eq!(error.location().end.offset, 36);
+}
+
+#[test]
+fn test_encoding_detection_invalid_sequences() {
+    // Lone high surrogate followed by "a"
+    let input = b"[\x00\"\x00\x00\xD8a\x00\"\x00]\x00";
+    let mut parser = ReaderJsonParser::new(input.as_slice()).with_encoding_detection();
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::StartArray);
+    let JsonParseError::Syntax(error) = parser.parse_next().unwrap_err() else {
+        unreachable!()
+    };
+    assert_eq!(error.location().start.offset, 4);
+    assert_eq!(error.location().end.offset, 6);
+
+    let mut parser = ReaderJsonParser::new(input.as_slice())
+        .with_encoding_detection()
+        .with_lossy_decoding();
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::StartArray);
+    assert_eq!(
+        parser.parse_next().unwrap(),
+        JsonEvent::String("\u{FFFD}a".into())
+    );
+    let warnings = parser.decoding_warnings();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].location().start.offset, 4);
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::EndArray);
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::Eof);
+
+    // Truncated code unit at the end
+    let input = b"[\x00]\x00\x00";
+    let mut parser = ReaderJsonParser::new(input.as_slice()).with_encoding_detection();
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::StartArray);
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::EndArray);
+    parser.parse_next().unwrap_err();
+}
+
+#[test]
+fn test_utf16_rejected_without_encoding_detection() {
+    let input = utf16("[1]", true, true);
+    ReaderJsonParser::new(input.as_slice())
+        .parse_next()
+        .unwrap_err();
+}
@@ -1,4 +1,4 @@
-use json_event_parser::{JsonEvent, SliceJsonParser, WriterJsonSerializer};
+use json_event_parser::{JsonEvent, ReaderJsonParser, SliceJsonParser, WriterJsonSerializer};
 
 #[test]
 fn test_recovery() {
@@ -9,6 +9,7 @@ fn test_recovery() {
         (b"{\"foo\":1,}", "{\"foo\":1}"),
         (b"{\"foo\" 1}", "{\"foo\":1}"),
         (b"[1 2]", "[1,2]"),
+        (b"{\"a\":1 \"b\":2}", "{\"a\":1,\"b\":2}"),
         (b"[\"\x00\"]", "[]"),
         (b"[\"\\uD888\\u1234\"]", "[]"),
     ];
@@ -33,6 +34,106 @@ fn test_recovery() {
     }
 }
 
+#[test]
+fn test_recovery_mode() {
+    let entries = [
+        (b"[nonono]".as_slice(), "[]", 1),
+        (b"[1,]", "[1]", 1),
+        (b"{\"foo\":1,}", "{\"foo\":1}", 1),
+        (b"{\"foo\" 1}", "{\"foo\":1}", 1),
+        (b"[1 2]", "[1,2]", 1),
+        (b"[1}", "[1]", 2),
+        (b"{1:2, \"a\": 3}", "{\"a\":3}", 1),
+        (b"{1:{\"b\": [{}]}, \"a\": 3}", "{\"a\":3}", 1),
+        (b"{\"a\":}", "{\"a\":null}", 2),
+        (b"{\"a\", \"b\": 1}", "{\"a\":null,\"b\":1}", 2),
+        (b"[\"a\": 1]", "[\"a\",1]", 2),
+        (b"[1] [2]", "[1]", 3),
+        (b"[1, {\"a\": [2", "[1,{\"a\":[2]}]", 1),
+        (b"{\"a\"", "{\"a\":null}", 1),
+        (b"[\"\x00\"]", "[]", 1),
+        (b"", "", 1),
+        (b"]", "", 1),
+        (b"[1, 2]", "[1,2]", 0),
+    ];
+
+    for (input, expected_output, expected_error_count) in entries {
+        for string_chunk_size in [None, Some(1)] {
+            let mut reader = SliceJsonParser::new(input).with_error_recovery();
+            if let Some(size) = string_chunk_size {
+                reader = reader.with_string_chunk_size(size);
+            }
+            let mut writer = WriterJsonSerializer::new(Vec::new());
+            loop {
+                match reader.parse_next().unwrap() {
+                    JsonEvent::Eof => break,
+                    event => writer.serialize_event(event).unwrap(),
+                }
+            }
+            assert_eq!(reader.parse_next().unwrap(), JsonEvent::Eof);
+            // The writer fails on empty documents
+            let actual_output = String::from_utf8(writer.finish().unwrap_or_default()).unwrap();
+            assert_eq!(
+                expected_output,
+                actual_output,
+                "on {}",
+                String::from_utf8_lossy(input)
+            );
+            assert_eq!(
+                reader.diagnostics().len(),
+                expected_error_count,
+                "on {}: {:?}",
+                String::from_utf8_lossy(input),
+                reader.diagnostics()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_recovery_mode_unterminated_string_chunks() {
+    let mut reader = SliceJsonParser::new(b"{\"a\": \"bc")
+        .with_error_recovery()
+        .with_string_chunk_size(1);
+    let mut writer = WriterJsonSerializer::new(Vec::new());
+    loop {
+        match reader.parse_next().unwrap() {
+            JsonEvent::Eof => break,
+            event => writer.serialize_event(event).unwrap(),
+        }
+    }
+    assert_eq!(writer.finish().unwrap(), b"{\"a\":\"bc\"}");
+    assert_eq!(reader.diagnostics().len(), 1);
+}
+
+#[test]
+fn test_recovery_mode_max_errors() {
+    let mut reader = SliceJsonParser::new(b"[1 2 3 4]").with_max_recovered_errors(2);
+    assert_eq!(reader.parse_next().unwrap(), JsonEvent::StartArray);
+    assert_eq!(reader.parse_next().unwrap(), JsonEvent::Number("1".into()));
+    assert_eq!(reader.parse_next().unwrap(), JsonEvent::Number("2".into()));
+    assert_eq!(reader.parse_next().unwrap(), JsonEvent::Number("3".into()));
+    let error = reader.parse_next().unwrap_err();
+    assert_eq!(error.location().start.offset, 7);
+    assert_eq!(reader.diagnostics().len(), 2);
+    assert_eq!(reader.parse_next().unwrap(), JsonEvent::Eof);
+}
+
+#[test]
+fn test_recovery_mode_peek() {
+    let mut reader = ReaderJsonParser::new(b"[1 {".as_slice()).with_error_recovery();
+    assert_eq!(reader.peek_next().unwrap(), &JsonEvent::StartArray);
+    assert_eq!(reader.parse_next().unwrap(), JsonEvent::StartArray);
+    assert_eq!(reader.peek_next().unwrap(), &JsonEvent::Number("1".into()));
+    assert_eq!(reader.parse_next().unwrap(), JsonEvent::Number("1".into()));
+    assert_eq!(reader.parse_next().unwrap(), JsonEvent::StartObject);
+    assert_eq!(reader.peek_next().unwrap(), &JsonEvent::EndObject);
+    assert_eq!(reader.parse_next().unwrap(), JsonEvent::EndObject);
+    assert_eq!(reader.parse_next().unwrap(), JsonEvent::EndArray);
+    assert_eq!(reader.parse_next().unwrap(), JsonEvent::Eof);
+    assert_eq!(reader.diagnostics().len(), 3);
+}
+
 #[test]
 fn test_error_messages() {
     let entries = [
@@ -67,3 +168,58 @@ fn test_error_messages() {
         );
     }
 }
+
+#[test]
+fn test_lossy_decoding() {
+    let entries = [
+        (b"[\"a\xFFb\"]".as_slice(), "[\"a\u{FFFD}b\"]", vec![3]),
+        (
+            b"[\"\xFF\xFE\xC3\"]",
+            "[\"\u{FFFD}\u{FFFD}\u{FFFD}\"]",
+            vec![2, 3, 4],
+        ),
+        (b"[\"\\uD888\\u1234\"]", "[\"\u{FFFD}\u{1234}\"]", vec![2]),
+        (
+            b"[\"\\uDC00\\uD800\"]",
+            "[\"\u{FFFD}\u{FFFD}\"]",
+            vec![2, 8],
+        ),
+        (b"{\"\xF0\x9F\x98\":1}", "{\"\u{FFFD}\":1}", vec![2]),
+    ];
+
+    for (input, expected_output, expected_warnings) in entries {
+        for string_chunk_size in [None, Some(1)] {
+            let mut reader = SliceJsonParser::new(input).with_lossy_decoding();
+            if let Some(size) = string_chunk_size {
+                reader = reader.with_string_chunk_size(size);
+            }
+            let mut writer = WriterJsonSerializer::new(Vec::new());
+            let mut warnings = Vec::new();
+            loop {
+                match reader.parse_next().unwrap() {
+                    JsonEvent::Eof => break,
+                    event => writer.serialize_event(event).unwrap(),
+                }
+                warnings.extend(
+                    reader
+                        .decoding_warnings()
+                        .iter()
+                        .map(|w| w.location().start.offset),
+                );
+            }
+            let actual_output = String::from_utf8(writer.finish().unwrap()).unwrap();
+            assert_eq!(
+                expected_output,
+                actual_output,
+                "on {}",
+                String::from_utf8_lossy(input)
+            );
+            assert_eq!(
+                expected_warnings,
+                warnings,
+                "on {}",
+                String::from_utf8_lossy(input)
+            );
+        }
+    }
+}
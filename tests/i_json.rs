@@ -0,0 +1,139 @@
+use json_event_parser::{JsonEvent, SliceJsonParser, WriterJsonSerializer};
+
+fn is_valid_i_json(json: &[u8]) -> bool {
+    let mut parser = SliceJsonParser::new(json).with_i_json_validation();
+    loop {
+        match parser.parse_next() {
+            Ok(JsonEvent::Eof) => return true,
+            Ok(_) => (),
+            Err(_) => return false,
+        }
+    }
+}
+
+#[test]
+fn test_i_json_parsing() {
+    for json in [
+        "[9007199254740991, -9007199254740991, 0, -0, 1.5e308, -1e-300, 0.0e-999999, 1.0e20]",
+        r#"{"a": {"a": 1}, "b": [{"a": 1}, {"a": 1}]}"#,
+        r#"["�", "é😀", "😀"]"#,
+    ] {
+        assert!(is_valid_i_json(json.as_bytes()), "{json}");
+    }
+    for json in [
+        "9007199254740992",
+        "-9007199254740992",
+        "100000000000000000000000",
+        "1e309",
+        "-1.5e400",
+        "1e-400",
+        r#"{"a": 1, "a": 2}"#,
+        r#"{"a\u0000": 1, "a\u0000": 2}"#,
+        r#""￿""#,
+        r#""﷐""#,
+        r#""􏿿""#,
+        "\"\u{1FFFE}\"",
+        r#"{"￾": 1}"#,
+    ] {
+        assert!(!is_valid_i_json(json.as_bytes()), "{json}");
+        let mut parser = SliceJsonParser::new(json.as_bytes());
+        while parser.parse_next().unwrap() != JsonEvent::Eof {}
+    }
+}
+
+#[test]
+fn test_i_json_parsing_with_repaired_strings() {
+    // Lone surrogates and invalid UTF-8 are rejected even if they are repaired
+    for json in [
+        br#"["\uD888"]"#.as_slice(),
+        br#"{"a\uDC00": 1}"#,
+        b"[\"a\xFFb\"]",
+    ] {
+        for string_chunk_size in [None, Some(1)] {
+            let mut parser = SliceJsonParser::new(json)
+                .with_i_json_validation()
+                .with_lone_surrogate_preservation()
+                .with_lossy_decoding();
+            if let Some(size) = string_chunk_size {
+                parser = parser.with_string_chunk_size(size);
+            }
+            let mut error_count = 0;
+            loop {
+                match parser.parse_next() {
+                    Ok(JsonEvent::Eof) => break,
+                    Ok(_) => (),
+                    Err(_) => error_count += 1,
+                }
+            }
+            assert_eq!(
+                error_count, 1,
+                "{json:?} with chunks of {string_chunk_size:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_i_json_serialization() {
+    let mut writer = WriterJsonSerializer::new(Vec::new()).with_i_json_validation();
+    writer.serialize_event(JsonEvent::StartArray).unwrap();
+    writer
+        .serialize_event(JsonEvent::Number("9007199254740992".into()))
+        .unwrap_err();
+    writer
+        .serialize_event(JsonEvent::String("\u{FDEF}".into()))
+        .unwrap_err();
+    writer.serialize_raw_value(b"[1e400]").unwrap_err();
+    writer
+        .serialize_raw_value(b"{\"a\":1,\"a\":1}")
+        .unwrap_err();
+    writer.serialize_raw_value(b"[1e300]").unwrap();
+    writer.start_string().unwrap();
+    writer.serialize_string_chunk("\u{FFFF}").unwrap_err();
+    writer.serialize_string_chunk("a").unwrap();
+    writer.end_string().unwrap();
+    writer.serialize_event(JsonEvent::StartObject).unwrap();
+    writer.start_object_key().unwrap();
+    writer.serialize_string_chunk("a").unwrap();
+    writer.serialize_string_chunk("b").unwrap();
+    writer.end_object_key().unwrap();
+    writer.serialize_event(JsonEvent::Null).unwrap();
+    writer
+        .serialize_event(JsonEvent::ObjectKey("ab".into()))
+        .unwrap_err();
+    writer
+        .serialize_event(JsonEvent::ObjectKey("a".into()))
+        .unwrap();
+    writer.serialize_event(JsonEvent::StartObject).unwrap();
+    writer
+        .serialize_event(JsonEvent::ObjectKey("a".into()))
+        .unwrap();
+    writer.serialize_event(JsonEvent::Null).unwrap();
+    writer.serialize_event(JsonEvent::EndObject).unwrap();
+    writer
+        .serialize_event(JsonEvent::ObjectKeyChunk("a".into()))
+        .unwrap();
+    writer
+        .serialize_event(JsonEvent::ObjectKey("c".into()))
+        .unwrap();
+    writer.serialize_event(JsonEvent::Null).unwrap();
+    writer.serialize_event(JsonEvent::EndObject).unwrap();
+    writer.serialize_event(JsonEvent::EndArray).unwrap();
+    assert_eq!(
+        String::from_utf8(writer.finish().unwrap()).unwrap(),
+        r#"[[1e300],"a",{"ab":null,"a":{"a":null},"ac":null}]"#
+    );
+
+    let mut writer = WriterJsonSerializer::new(Vec::new()).with_i_json_validation();
+    writer.serialize_event(JsonEvent::StartObject).unwrap();
+    writer
+        .serialize_event(JsonEvent::ObjectKey("ab".into()))
+        .unwrap();
+    writer.serialize_event(JsonEvent::Null).unwrap();
+    writer
+        .serialize_event(JsonEvent::ObjectKeyChunk("a".into()))
+        .unwrap();
+    writer
+        .serialize_event(JsonEvent::ObjectKey("b".into()))
+        .unwrap_err();
+}
@@ -0,0 +1,211 @@
+use json_event_parser::{
+    BufReaderJsonParser, JsonEvent, JsonLimit, JsonLimits, JsonParseError, ReaderJsonParser,
+    SliceJsonParser,
+};
+
+const JSON: &str = r#"{"key": ["a\"bc", -12.5e3, true, null, {"a": 1, "b": 2}], "other": []}"#;
+
+/// Returns the limit exceeded when parsing `JSON` with both the slice and the reader parsers
+fn first_limit_error(limits: JsonLimits, string_chunk_size: Option<usize>) -> Option<JsonLimit> {
+    let mut parser = SliceJsonParser::new(JSON.as_bytes()).with_limits(limits);
+    let mut reader = ReaderJsonParser::new(JSON.as_bytes())
+        .with_limits(limits)
+        .with_max_buffer_size(32);
+    if let Some(size) = string_chunk_size {
+        parser = parser.with_string_chunk_size(size);
+        reader = reader.with_string_chunk_size(size);
+    }
+    let mut slice_error = None;
+    loop {
+        match parser.parse_next() {
+            Ok(JsonEvent::Eof) => break,
+            Ok(_) => (),
+            Err(e) => {
+                assert!(slice_error.is_none(), "The parsing must stop after a limit");
+                slice_error = Some(e.exceeded_limit().unwrap());
+            }
+        }
+    }
+    let mut reader_error = None;
+    loop {
+        match reader.parse_next() {
+            Ok(JsonEvent::Eof) => break,
+            Ok(_) => (),
+            Err(JsonParseError::Syntax(e)) => {
+                assert!(
+                    reader_error.is_none(),
+                    "The parsing must stop after a limit"
+                );
+                reader_error = Some(e.exceeded_limit().unwrap());
+            }
+            Err(JsonParseError::Io(e)) => panic!("{e}"),
+        }
+    }
+    assert_eq!(slice_error, reader_error);
+    slice_error
+}
+
+#[test]
+fn test_limits() {
+    for string_chunk_size in [None, Some(1), Some(2)] {
+        assert_eq!(
+            first_limit_error(JsonLimits::new(), string_chunk_size),
+            None
+        );
+        for (limits, expected) in [
+            (JsonLimits::new().with_max_string_length(5), None),
+            (
+                JsonLimits::new().with_max_string_length(4),
+                Some(JsonLimit::StringLength),
+            ),
+            (JsonLimits::new().with_max_number_length(7), None),
+            (
+                JsonLimits::new().with_max_number_length(6),
+                Some(JsonLimit::NumberLength),
+            ),
+            (
+                JsonLimits::new().with_max_document_size(JSON.len().try_into().unwrap()),
+                None,
+            ),
+            (
+                JsonLimits::new().with_max_document_size((JSON.len() - 1).try_into().unwrap()),
+                Some(JsonLimit::DocumentSize),
+            ),
+            (JsonLimits::new().with_max_object_members(2), None),
+            (
+                JsonLimits::new().with_max_object_members(1),
+                Some(JsonLimit::ObjectMembers),
+            ),
+            (JsonLimits::new().with_max_array_elements(5), None),
+            (
+                JsonLimits::new().with_max_array_elements(4),
+                Some(JsonLimit::ArrayElements),
+            ),
+        ] {
+            assert_eq!(
+                first_limit_error(limits, string_chunk_size),
+                expected,
+                "{limits:?} with chunks of {string_chunk_size:?}"
+            );
+        }
+    }
+    assert_eq!(
+        first_limit_error(JsonLimits::new().with_max_event_count(18), None),
+        None
+    );
+    assert_eq!(
+        first_limit_error(JsonLimits::new().with_max_event_count(17), None),
+        Some(JsonLimit::EventCount)
+    );
+}
+
+#[test]
+fn test_limits_on_unbounded_input() {
+    // The limits are enforced before the whole token is read
+    for (input, limits, expected) in [
+        (
+            b"\"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".as_slice(),
+            JsonLimits::new().with_max_string_length(10),
+            JsonLimit::StringLength,
+        ),
+        (
+            b"1111111111111111111111111111111111111111111111111111111111111111111",
+            JsonLimits::new().with_max_number_length(10),
+            JsonLimit::NumberLength,
+        ),
+        (
+            b"[                                                                  ",
+            JsonLimits::new().with_max_document_size(10),
+            JsonLimit::DocumentSize,
+        ),
+    ] {
+        let mut reader = ReaderJsonParser::new(input)
+            .with_limits(limits)
+            .with_max_buffer_size(20);
+        let error = loop {
+            match reader.parse_next() {
+                Ok(JsonEvent::Eof) => panic!("An error is expected"),
+                Ok(_) => (),
+                Err(JsonParseError::Syntax(e)) => break e,
+                Err(JsonParseError::Io(e)) => panic!("{e}"),
+            }
+        };
+        assert_eq!(error.exceeded_limit(), Some(expected), "{error}");
+    }
+}
+
+#[test]
+fn test_limits_with_peek() {
+    // Peeked events are only counted once
+    for (input, limits, expected) in [
+        (
+            b"[1, 2]".as_slice(),
+            JsonLimits::new().with_max_array_elements(2),
+            None,
+        ),
+        (
+            b"[1, 2, 3]",
+            JsonLimits::new().with_max_array_elements(2),
+            Some(JsonLimit::ArrayElements),
+        ),
+        (
+            b"{\"a\": 1, \"b\": 2}",
+            JsonLimits::new().with_max_object_members(2),
+            None,
+        ),
+        (
+            b"[1, 2, 3]",
+            JsonLimits::new().with_max_event_count(5),
+            None,
+        ),
+        (
+            b"[1, 2, 3]",
+            JsonLimits::new().with_max_event_count(4),
+            Some(JsonLimit::EventCount),
+        ),
+    ] {
+        let mut parser = SliceJsonParser::new(input).with_limits(limits);
+        let slice_error = loop {
+            let peeked = match parser.peek_next() {
+                Ok(event) => event.clone(),
+                Err(e) => break e.exceeded_limit(),
+            };
+            let event = parser.parse_next().unwrap();
+            assert_eq!(event, peeked);
+            if event == JsonEvent::Eof {
+                break None;
+            }
+        };
+        assert_eq!(slice_error, expected, "{limits:?}");
+
+        let mut reader = ReaderJsonParser::new(input).with_limits(limits);
+        let reader_error = loop {
+            let peeked = match reader.peek_next() {
+                Ok(event) => event.clone(),
+                Err(JsonParseError::Syntax(e)) => break e.exceeded_limit(),
+                Err(JsonParseError::Io(e)) => panic!("{e}"),
+            };
+            let event = reader.parse_next().unwrap();
+            assert_eq!(event, peeked);
+            if event == JsonEvent::Eof {
+                break None;
+            }
+        };
+        assert_eq!(reader_error, expected, "{limits:?}");
+
+        let mut reader = BufReaderJsonParser::new(input).with_limits(limits);
+        let buf_reader_error = loop {
+            let peeked = match reader.peek_next() {
+                Ok(event) => event.clone(),
+                Err(JsonParseError::Syntax(e)) => break e.exceeded_limit(),
+                Err(JsonParseError::Io(e)) => panic!("{e}"),
+            };
+            let event = reader.parse_next().unwrap();
+            assert_eq!(event, peeked);
+            if event == JsonEvent::Eof {
+                break None;
+            }
+        };
+        assert_eq!(buf_reader_error, expected, "{limits:?}");
+    }
+}
@@ -0,0 +1,120 @@
+use json_event_parser::{
+    JsonEvent, LoneSurrogate, ReaderJsonParser, SliceJsonParser, WriterJsonSerializer,
+};
+
+fn roundtrip(input: &str, string_chunk_size: Option<usize>) -> String {
+    let mut reader = SliceJsonParser::new(input.as_bytes()).with_lone_surrogate_preservation();
+    if let Some(size) = string_chunk_size {
+        reader = reader.with_string_chunk_size(size);
+    }
+    let mut writer = WriterJsonSerializer::new(Vec::new());
+    loop {
+        match reader.parse_next().unwrap() {
+            JsonEvent::Eof => break,
+            event => writer
+                .serialize_event_with_lone_surrogates(event, reader.lone_surrogates())
+                .unwrap(),
+        }
+    }
+    String::from_utf8(writer.finish().unwrap()).unwrap()
+}
+
+#[test]
+fn test_lone_surrogates_roundtrip() {
+    for (input, expected) in [
+        (r#""\ud888""#, r#""\ud888""#),
+        (r#""\uDC00""#, r#""\uDC00""#),
+        (r#""\uDc0a""#, r#""\uDc0a""#),
+        (r#""a\ud888\ud888b""#, r#""a\ud888\ud888b""#),
+        (r#""\ud888A""#, r#""\ud888A""#),
+        (r#""😀\ude00""#, r#""😀\ude00""#),
+        (r#""�\ud888�""#, r#""�\ud888�""#),
+        (
+            r#"{"k\udc00ey": ["\ud888", "􏿿"], "\ud800": 1}"#,
+            r#"{"k\udc00ey":["\ud888","􏿿"],"\ud800":1}"#,
+        ),
+    ] {
+        assert_eq!(roundtrip(input, None), expected, "{input}");
+        for chunk_size in 1..8 {
+            assert_eq!(roundtrip(input, Some(chunk_size)), expected, "{input}");
+        }
+        assert!(SliceJsonParser::new(input.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .is_err());
+    }
+}
+
+#[test]
+fn test_lone_surrogates_reader() {
+    let mut reader = ReaderJsonParser::new(r#"["\udead", "a", "𐀀"]"#.as_bytes())
+        .with_lone_surrogate_preservation();
+    assert_eq!(reader.parse_next().unwrap(), JsonEvent::StartArray);
+    assert!(reader.lone_surrogates().is_empty());
+    assert_eq!(
+        reader.parse_next().unwrap(),
+        JsonEvent::String("\u{FFFD}".into())
+    );
+    assert_eq!(
+        reader.lone_surrogates(),
+        [LoneSurrogate {
+            offset: 0,
+            code_unit: 0xDEAD,
+            hex_digits: *b"dead"
+        }]
+    );
+    assert_eq!(reader.parse_next().unwrap(), JsonEvent::String("a".into()));
+    assert!(reader.lone_surrogates().is_empty());
+    assert_eq!(
+        reader.parse_next().unwrap(),
+        JsonEvent::String("\u{10000}".into())
+    );
+    assert!(reader.lone_surrogates().is_empty());
+}
+
+#[test]
+fn test_lone_surrogates_serialization_errors() {
+    let surrogate = LoneSurrogate {
+        offset: 1,
+        code_unit: 0xD800,
+        hex_digits: *b"D800",
+    };
+    for (event, lone_surrogates) in [
+        (JsonEvent::Null, vec![surrogate]),
+        (
+            JsonEvent::String("a\u{FFFD}".into()),
+            vec![surrogate, surrogate],
+        ),
+        (JsonEvent::String("\u{FFFD}".into()), vec![surrogate]),
+        (
+            JsonEvent::String("a\u{FFFD}".into()),
+            vec![LoneSurrogate {
+                offset: 1,
+                code_unit: 0x41,
+                hex_digits: *b"0041",
+            }],
+        ),
+        (
+            JsonEvent::String("a\u{FFFD}".into()),
+            vec![LoneSurrogate {
+                hex_digits: *b"D801",
+                ..surrogate
+            }],
+        ),
+        (
+            JsonEvent::String("a\u{FFFD}".into()),
+            vec![LoneSurrogate {
+                hex_digits: *b"D8\"0",
+                ..surrogate
+            }],
+        ),
+    ] {
+        let mut writer = WriterJsonSerializer::new(Vec::new());
+        assert!(writer
+            .serialize_event_with_lone_surrogates(event, &lone_surrogates)
+            .is_err());
+    }
+    let mut writer = WriterJsonSerializer::new(Vec::new()).with_i_json_validation();
+    assert!(writer
+        .serialize_event_with_lone_surrogates(JsonEvent::String("a\u{FFFD}".into()), &[surrogate])
+        .is_err());
+}
@@ -0,0 +1,64 @@
+use json_event_parser::{
+    JsonEvent, JsonPath, ReaderJsonParser, SliceJsonParser, WriterJsonSerializer,
+};
+
+fn to_json(events: Vec<JsonEvent<'_>>) -> String {
+    let mut writer = WriterJsonSerializer::new(Vec::new());
+    for event in events {
+        writer.serialize_event(event).unwrap();
+    }
+    String::from_utf8(writer.finish().unwrap()).unwrap()
+}
+
+fn select(path: &str, json: &str) -> Vec<String> {
+    JsonPath::parse(path)
+        .unwrap()
+        .select(SliceJsonParser::new(json.as_bytes()))
+        .map(|events| to_json(events.unwrap()))
+        .collect()
+}
+
+#[test]
+fn test_path_selection() {
+    let json =
+        r#"{"a": [{"b": 1, "c": {"b": 2}}, [3, 4, 5, 6], "x"], "b": {"b": true}, "a b": null}"#;
+    assert_eq!(select("$", "1"), ["1"]);
+    assert_eq!(select("$.a[2]", json), ["\"x\""]);
+    assert_eq!(select("$.a[1][1::2]", json), ["4", "6"]);
+    assert_eq!(select("$.a[1][:2]", json), ["3", "4"]);
+    assert_eq!(select("$.a[1][0:4:0]", json), Vec::<String>::new());
+    assert_eq!(select("$['a b', \"b\"]", json), ["{\"b\":true}", "null"]);
+    assert_eq!(select("$.a[0].*", json), ["1", "{\"b\":2}"]);
+    assert_eq!(select("$..b", json), ["1", "2", "{\"b\":true}", "true"]);
+    assert_eq!(select("$..[3]", json), ["6"]);
+    assert_eq!(select("$.a[5].b", json), Vec::<String>::new());
+}
+
+#[test]
+fn test_path_selection_reader() {
+    let json = br#"{"results": [{"id": 1}, {"id": 2, "other": [1, 2]}]}"#;
+    let ids = JsonPath::parse("$.results[*].id")
+        .unwrap()
+        .select(ReaderJsonParser::new(json.as_slice()))
+        .map(|events| to_json(events.unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(ids, ["1", "2"]);
+}
+
+#[test]
+fn test_path_syntax_errors() {
+    for path in [
+        "", "a", "$.", "$[", "$[1", "$[01]", "$[-1]", "$[?@.a]", "$['a", "$['\\x']", "$.1a",
+    ] {
+        assert!(JsonPath::parse(path).is_err(), "{path}");
+    }
+    for path in [
+        "$.a.b",
+        "$['\\u00e9\\uD83D\\uDE00']",
+        "$..*",
+        "$[ 1 , 'a' ]",
+        "$.é",
+    ] {
+        assert!(JsonPath::parse(path).is_ok(), "{path}");
+    }
+}
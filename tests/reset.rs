@@ -0,0 +1,97 @@
+use json_event_parser::{
+    JsonEvent, JsonLimits, JsonValue, LowLevelJsonSerializer, ReaderJsonParser,
+    WriterJsonSerializer,
+};
+
+#[test]
+fn test_reader_reset_in_the_middle_of_documents() {
+    let mut reader = ReaderJsonParser::new(b"".as_slice())
+        .with_duplicate_object_key_detection()
+        .with_string_chunk_size(2)
+        .with_limits(JsonLimits::new().with_max_event_count(8));
+    for input in [
+        br#"{"a": "long string", "b": [1, {"c": null}]}"#.as_slice(),
+        br#"{"a": [true, "foo"]}"#,
+        br#"["a", "b"#,
+        b"\xEF\xBB\xBF  \n {",
+    ] {
+        reader.reset_with_reader(input);
+        // We stop in the middle of the document
+        for _ in 0..4 {
+            if reader.parse_next().is_err() {
+                break;
+            }
+        }
+    }
+
+    // The state of the previous documents does not leak into the new one
+    reader.reset_with_reader(br#"{"a": 1}"#.as_slice());
+    assert_eq!(reader.position().offset, 0);
+    assert_eq!(
+        JsonValue::from_events(&mut reader).unwrap(),
+        JsonValue::Object(vec![("a".into(), JsonValue::Number("1".into()))])
+    );
+    assert_eq!(reader.position().offset, 8);
+    assert_eq!(reader.position().line, 0);
+
+    // The limits are kept
+    reader.reset_with_reader(b"[1, 2, 3, 4, 5, 6, 7, 8]".as_slice());
+    assert!(JsonValue::from_events(&mut reader).is_err());
+}
+
+#[test]
+fn test_reader_reset_after_error_and_recovery() {
+    let mut reader = ReaderJsonParser::new(b"[1 2".as_slice())
+        .with_error_recovery()
+        .with_encoding_detection();
+    while reader.parse_next().unwrap() != JsonEvent::Eof {}
+    assert_eq!(reader.diagnostics().len(), 2);
+
+    reader.reset_with_reader(b"[\x001\x00]\x00".as_slice());
+    assert!(reader.diagnostics().is_empty());
+    assert_eq!(reader.parse_next().unwrap(), JsonEvent::StartArray);
+    assert_eq!(reader.parse_next().unwrap(), JsonEvent::Number("1".into()));
+    assert_eq!(reader.parse_next().unwrap(), JsonEvent::EndArray);
+    assert_eq!(reader.parse_next().unwrap(), JsonEvent::Eof);
+    assert!(reader.diagnostics().is_empty());
+}
+
+#[test]
+fn test_serializer_reset() {
+    let mut writer = WriterJsonSerializer::new(Vec::new()).with_i_json_validation();
+    writer.serialize_event(JsonEvent::StartObject).unwrap();
+    writer
+        .serialize_event(JsonEvent::ObjectKey("a".into()))
+        .unwrap();
+    writer.start_base64_string().unwrap();
+    writer.serialize_base64_chunk(b"a").unwrap();
+    assert_eq!(writer.reset_with_writer(Vec::new()), b"{\"a\":\"");
+
+    // The open object, the tracked keys and the pending base64 byte are dropped
+    writer.serialize_event(JsonEvent::StartObject).unwrap();
+    writer
+        .serialize_event(JsonEvent::ObjectKey("a".into()))
+        .unwrap();
+    writer.start_base64_string().unwrap();
+    writer.serialize_base64_chunk(b"b").unwrap();
+    writer.end_base64_string().unwrap();
+    writer.serialize_event(JsonEvent::EndObject).unwrap();
+    assert_eq!(writer.finish().unwrap(), b"{\"a\":\"Yg==\"}");
+
+    let mut writer = LowLevelJsonSerializer::new();
+    let mut output = Vec::new();
+    writer
+        .serialize_event(JsonEvent::StartArray, &mut output)
+        .unwrap();
+    writer
+        .serialize_event(JsonEvent::Null, &mut output)
+        .unwrap();
+    writer.reset();
+    output.clear();
+    writer
+        .serialize_event(JsonEvent::Null, &mut output)
+        .unwrap();
+    writer
+        .serialize_event(JsonEvent::Null, &mut output)
+        .unwrap_err();
+}
@@ -0,0 +1,189 @@
+#![cfg(feature = "serde")]
+
+#[cfg(feature = "async-tokio")]
+use json_event_parser::TokioAsyncWriterJsonSerializer;
+use json_event_parser::{JsonEvent, ReaderJsonParser, SliceJsonParser, WriterJsonSerializer};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Point(i32, i32),
+    Rectangle { width: u32, height: u32 },
+}
+
+#[derive(Deserialize, PartialEq, Debug)]
+struct Record<'a> {
+    #[serde(borrow)]
+    name: Cow<'a, str>,
+    tags: Vec<String>,
+    score: Option<f32>,
+    shapes: Vec<Shape>,
+    counts: BTreeMap<u8, i128>,
+    flag: bool,
+    unit: (),
+}
+
+#[derive(Deserialize, Debug)]
+#[allow(dead_code)]
+struct Nested(Vec<Nested>);
+
+#[test]
+fn test_deserialize_slice() {
+    let json = br#"{
+        "name": "foo",
+        "ignored": {"a": [1, {"b": null}]},
+        "tags": ["a", "b\n"],
+        "score": null,
+        "shapes": ["Empty", {"Circle": 1.5}, {"Point": [1, -2]}, {"Rectangle": {"width": 1, "height": 2}}],
+        "counts": {"1": -170141183460469231731687303715884105728},
+        "flag": true,
+        "unit": null
+    }"#;
+    let mut parser = SliceJsonParser::new(json);
+    let record = parser.deserialize_next::<Record<'_>>().unwrap();
+    assert!(matches!(record.name, Cow::Borrowed("foo")));
+    assert_eq!(
+        record,
+        Record {
+            name: "foo".into(),
+            tags: vec!["a".into(), "b\n".into()],
+            score: None,
+            shapes: vec![
+                Shape::Empty,
+                Shape::Circle(1.5),
+                Shape::Point(1, -2),
+                Shape::Rectangle {
+                    width: 1,
+                    height: 2
+                }
+            ],
+            counts: [(1, i128::MIN)].into_iter().collect(),
+            flag: true,
+            unit: ()
+        }
+    );
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::Eof);
+}
+
+#[test]
+fn test_deserialize_reader_in_stream() {
+    let json = br#"{"items": [[1, "a"], [2, "b"]], "other": 3}"#;
+    let mut parser = ReaderJsonParser::new(json.as_slice());
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::StartObject);
+    assert_eq!(
+        parser.parse_next().unwrap(),
+        JsonEvent::ObjectKey("items".into())
+    );
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::StartArray);
+    let mut items = Vec::new();
+    while parser.peek_next().unwrap() != &JsonEvent::EndArray {
+        items.push(parser.deserialize_next::<(u64, String)>().unwrap());
+    }
+    assert_eq!(items, [(1, "a".into()), (2, "b".into())]);
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::EndArray);
+    assert_eq!(
+        parser.parse_next().unwrap(),
+        JsonEvent::ObjectKey("other".into())
+    );
+    assert_eq!(parser.deserialize_next::<Option<u8>>().unwrap(), Some(3));
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::EndObject);
+}
+
+#[test]
+fn test_deserialize_errors() {
+    assert!(SliceJsonParser::new(b"\"a\"")
+        .deserialize_next::<u32>()
+        .is_err());
+    assert!(SliceJsonParser::new(b"[1, 2, 3]")
+        .deserialize_next::<(u8, u8)>()
+        .is_err());
+    assert!(SliceJsonParser::new(b"[1, 2")
+        .deserialize_next::<Vec<u8>>()
+        .is_err());
+    assert!(SliceJsonParser::new(b"256")
+        .deserialize_next::<u8>()
+        .is_err());
+    assert!(SliceJsonParser::new(b"{\"Circle\": 1, \"Empty\": null}")
+        .deserialize_next::<Shape>()
+        .is_err());
+    assert!(SliceJsonParser::new(b"")
+        .deserialize_next::<Option<u8>>()
+        .is_err());
+
+    let mut deep = "[".repeat(1000);
+    deep.push_str(&"]".repeat(1000));
+    assert!(SliceJsonParser::new(deep.as_bytes())
+        .deserialize_next::<serde::de::IgnoredAny>()
+        .is_ok());
+    let error = SliceJsonParser::new(deep.as_bytes())
+        .deserialize_next::<Nested>()
+        .unwrap_err();
+    assert!(error.to_string().contains("depth"), "{error}");
+}
+
+#[test]
+fn test_serialize_roundtrip() {
+    let shapes = vec![
+        Shape::Empty,
+        Shape::Circle(1.5),
+        Shape::Point(1, -2),
+        Shape::Rectangle {
+            width: 1,
+            height: 2,
+        },
+    ];
+    let mut writer = WriterJsonSerializer::new(Vec::new());
+    writer.serialize_value(&shapes).unwrap();
+    let json = writer.finish().unwrap();
+    assert_eq!(
+        json,
+        br#"["Empty",{"Circle":1.5},{"Point":[1,-2]},{"Rectangle":{"width":1,"height":2}}]"#
+    );
+    assert_eq!(
+        SliceJsonParser::new(&json)
+            .deserialize_next::<Vec<Shape>>()
+            .unwrap(),
+        shapes
+    );
+}
+
+#[test]
+fn test_serialize_errors() {
+    let mut writer = WriterJsonSerializer::new(Vec::new());
+    assert!(writer
+        .serialize_value(&BTreeMap::from([((1, 2), 3)]))
+        .is_err());
+    let mut writer = WriterJsonSerializer::new(Vec::new());
+    writer.serialize_value(&1).unwrap();
+    assert!(writer.serialize_value(&2).is_err());
+}
+
+#[test]
+fn test_serialize_error_keeps_output_consistent() {
+    let mut writer = WriterJsonSerializer::new(Vec::new());
+    writer.serialize_event(JsonEvent::StartArray).unwrap();
+    assert!(writer
+        .serialize_value(&BTreeMap::from([((1, 2), 3)]))
+        .is_err());
+    writer.serialize_event(JsonEvent::EndObject).unwrap();
+    writer.serialize_event(JsonEvent::EndArray).unwrap();
+    assert_eq!(writer.finish().unwrap(), b"[{}]");
+}
+
+#[cfg(feature = "async-tokio")]
+#[tokio::test]
+async fn test_tokio_async_serialize_error_keeps_output_consistent() {
+    let mut writer = TokioAsyncWriterJsonSerializer::new(Vec::new());
+    writer.serialize_event(JsonEvent::StartArray).await.unwrap();
+    assert!(writer
+        .serialize_value(&BTreeMap::from([((1, 2), 3)]))
+        .await
+        .is_err());
+    writer.serialize_event(JsonEvent::EndObject).await.unwrap();
+    writer.serialize_event(JsonEvent::EndArray).await.unwrap();
+    assert_eq!(writer.finish().unwrap(), b"[{}]");
+}
@@ -0,0 +1,123 @@
+use json_event_parser::{JsonEvent, LowLevelJsonParser, ReaderJsonParser};
+
+const JSON: &[u8] =
+    r#"{"key": [1, "long string", {"a": true, "b": null}], "k2": "é😀", "k3": -1.5e3}"#.as_bytes();
+
+/// Parses `input` and returns the events followed by the recovered errors, with a snapshot taken and restored in a new parser after the given number of events.
+fn parse_with_resume(
+    input: &[u8],
+    build_parser: impl Fn() -> LowLevelJsonParser,
+    resume_after: usize,
+) -> Vec<Result<JsonEvent<'static>, String>> {
+    let mut parser = build_parser();
+    let mut offset = 0;
+    let mut events = Vec::new();
+    loop {
+        if events.len() == resume_after {
+            let snapshot = parser.snapshot();
+            assert_eq!(parser.position().offset, u64::try_from(offset).unwrap());
+            parser = build_parser();
+            parser.restore_snapshot(&snapshot).unwrap();
+        }
+        let result = parser.parse_next(&input[offset..], true);
+        offset += result.consumed_bytes;
+        match result.event {
+            Some(Ok(JsonEvent::Eof)) => {
+                events.extend(parser.diagnostics().iter().map(|e| Err(e.to_string())));
+                return events;
+            }
+            Some(event) => events.push(event.map(JsonEvent::into_owned).map_err(|e| e.to_string())),
+            None => (),
+        }
+    }
+}
+
+#[test]
+fn test_snapshot_resume() {
+    for build_parser in [
+        LowLevelJsonParser::new as fn() -> LowLevelJsonParser,
+        || LowLevelJsonParser::new().with_string_chunk_size(2),
+        || LowLevelJsonParser::new().with_duplicate_object_key_detection(),
+        || LowLevelJsonParser::new().with_error_recovery(),
+    ] {
+        let expected = parse_with_resume(JSON, build_parser, usize::MAX);
+        for resume_after in 0..expected.len() {
+            assert_eq!(
+                parse_with_resume(JSON, build_parser, resume_after),
+                expected,
+                "resumed after {resume_after} events"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_snapshot_resume_with_errors() {
+    let input = br#"{"a": 1, "a": [1 2, }"#;
+    for build_parser in [
+        || LowLevelJsonParser::new().with_duplicate_object_key_detection(),
+        || {
+            LowLevelJsonParser::new()
+                .with_duplicate_object_key_detection()
+                .with_error_recovery()
+        },
+    ] {
+        let expected = parse_with_resume(input, build_parser, usize::MAX);
+        assert!(expected.iter().any(Result::is_err));
+        for resume_after in 0..expected.len() {
+            assert_eq!(
+                parse_with_resume(input, build_parser, resume_after),
+                expected,
+                "resumed after {resume_after} events"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_snapshot_with_peeked_event() {
+    let mut reader = ReaderJsonParser::new(JSON);
+    assert_eq!(reader.parse_next().unwrap(), JsonEvent::StartObject);
+    assert_eq!(
+        reader.peek_next().unwrap(),
+        &JsonEvent::ObjectKey("key".into())
+    );
+    let snapshot = reader.snapshot();
+    let offset = usize::try_from(reader.position().offset).unwrap();
+
+    let mut reader = ReaderJsonParser::new(&JSON[offset..]);
+    reader.restore_snapshot(&snapshot).unwrap();
+    assert_eq!(
+        reader.parse_next().unwrap(),
+        JsonEvent::ObjectKey("key".into())
+    );
+    assert_eq!(reader.parse_next().unwrap(), JsonEvent::StartArray);
+    assert_eq!(reader.position().line, 0);
+}
+
+#[test]
+fn test_snapshot_errors() {
+    let snapshot = LowLevelJsonParser::new().snapshot();
+    let mut parser = LowLevelJsonParser::new();
+    parser.restore_snapshot(b"").unwrap_err();
+    parser.restore_snapshot(b"foo").unwrap_err();
+    parser
+        .restore_snapshot(&snapshot[..snapshot.len() - 1])
+        .unwrap_err();
+    let mut extended = snapshot.clone();
+    extended.push(0);
+    parser.restore_snapshot(&extended).unwrap_err();
+    parser.restore_snapshot(&snapshot).unwrap();
+
+    LowLevelJsonParser::new()
+        .with_duplicate_object_key_detection()
+        .restore_snapshot(&snapshot)
+        .unwrap_err();
+    LowLevelJsonParser::new()
+        .restore_snapshot(&LowLevelJsonParser::new().with_error_recovery().snapshot())
+        .unwrap_err();
+    ReaderJsonParser::new(JSON)
+        .with_encoding_detection()
+        .restore_snapshot(&snapshot)
+        .unwrap_err();
+}
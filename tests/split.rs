@@ -0,0 +1,75 @@
+use json_event_parser::{JsonEvent, ReaderJsonArraySplitter, ReaderJsonParser};
+use std::io::{self, Read};
+
+/// Returns the input one byte at a time
+struct SlowReader<'a>(&'a [u8]);
+
+impl Read for SlowReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.0.is_empty() || buf.is_empty() {
+            return Ok(0);
+        }
+        buf[0] = self.0[0];
+        self.0 = &self.0[1..];
+        Ok(1)
+    }
+}
+
+#[test]
+fn test_raw_elements() {
+    let file = b"\xEF\xBB\xBF [ 1 ,\r\n\"a\\\"b\" , { \"c\" : [ true, null ] } ,[], -1.5e3\n]";
+    let mut splitter = ReaderJsonArraySplitter::new(ReaderJsonParser::new(SlowReader(file)));
+    let mut elements = Vec::new();
+    while let Some(element) = splitter.next_raw_element().unwrap() {
+        elements.push(String::from_utf8(element).unwrap());
+    }
+    assert_eq!(
+        elements,
+        [
+            "1",
+            "\"a\\\"b\"",
+            "{ \"c\" : [ true, null ] }",
+            "[]",
+            "-1.5e3"
+        ]
+    );
+    assert_eq!(splitter.into_parser().parse_next().unwrap(), JsonEvent::Eof);
+}
+
+#[test]
+fn test_mixed_elements() {
+    let file = br#"{"a": [0, {"b": [[1, 2], "x", [3]]}]}"#;
+    let mut splitter =
+        ReaderJsonArraySplitter::new(ReaderJsonParser::new(file.as_slice())).with_pointer("/a/1/b");
+    let mut element = splitter.next_element().unwrap().unwrap();
+    assert_eq!(element.parse_next().unwrap(), JsonEvent::StartArray);
+    assert_eq!(element.parse_next().unwrap(), JsonEvent::Number("1".into()));
+    let mut element = splitter.next_element().unwrap().unwrap();
+    assert_eq!(element.parse_next().unwrap(), JsonEvent::String("x".into()));
+    assert_eq!(element.parse_next().unwrap(), JsonEvent::Eof);
+    assert_eq!(splitter.next_raw_element().unwrap().unwrap(), b"[3]");
+    assert!(splitter.next_element().unwrap().is_none());
+    let mut parser = splitter.into_parser();
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::EndObject);
+    assert_eq!(parser.parse_next().unwrap(), JsonEvent::EndArray);
+}
+
+#[test]
+fn test_splitter_errors() {
+    for (file, pointer) in [
+        (b"{\"a\": 1}".as_slice(), ""),
+        (b"{\"a\": 1}", "/a"),
+        (b"{\"a\": []}", "/b"),
+        (b"[[]]", "/1"),
+        (b"[[]]", "a"),
+        (b"[1, 2", ""),
+    ] {
+        let mut splitter =
+            ReaderJsonArraySplitter::new(ReaderJsonParser::new(file)).with_pointer(pointer);
+        let result = (|| {
+            while splitter.next_raw_element()?.is_some() {}
+            io::Result::Ok(())
+        })();
+        assert!(result.is_err(), "{pointer}");
+    }
+}
@@ -44,6 +44,12 @@ const OTHER_INVALID_TESTS: [&str; 23] = [
     "i_string_UTF-16LE_with_BOM.json",
 ];
 
+/// The implementation defined tests that are valid under the I-JSON profile
+const I_JSON_VALID_TESTS: [&str; 2] = [
+    "i_structure_500_nested_arrays.json",
+    "i_structure_UTF-8_BOM_empty_object.json",
+];
+
 #[test]
 fn test_testsuite_parsing() -> Result<()> {
     for file in read_dir(format!(
@@ -92,6 +98,32 @@ fn test_testsuite_parsing() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_testsuite_i_json_parsing() -> Result<()> {
+    for file in read_dir(format!(
+        "{}/JSONTestSuite/test_parsing",
+        env!("CARGO_MANIFEST_DIR")
+    ))? {
+        let file = file?;
+        let file_name = file.file_name().to_str().unwrap().to_owned();
+        if !file_name.starts_with("i_") || !file_name.ends_with(".json") {
+            continue;
+        }
+        let result = parse_i_json_read_result(File::open(file.path())?);
+        if I_JSON_VALID_TESTS.contains(&file_name.as_ref()) {
+            if let Err(error) = result {
+                panic!("I-JSON parsing of {file_name} failed with error {error}")
+            }
+        } else if let Ok(json) = result {
+            panic!(
+                "I-JSON parsing of {file_name} wrongly succeeded with json {}",
+                str::from_utf8(&json).unwrap()
+            )
+        }
+    }
+    Ok(())
+}
+
 fn parse_buffer_result(read: &[u8]) -> Result<Vec<u8>> {
     let mut reader = SliceJsonParser::new(read);
     let mut writer = WriterJsonSerializer::new(Vec::new());
@@ -113,3 +145,14 @@ fn parse_read_result(read: impl Read) -> Result<Vec<u8>> {
         }
     }
 }
+
+fn parse_i_json_read_result(read: impl Read) -> Result<Vec<u8>> {
+    let mut reader = ReaderJsonParser::new(read).with_i_json_validation();
+    let mut writer = WriterJsonSerializer::new(Vec::new()).with_i_json_validation();
+    loop {
+        match reader.parse_next()? {
+            JsonEvent::Eof => return writer.finish(),
+            e => writer.serialize_event(e)?,
+        }
+    }
+}
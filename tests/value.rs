@@ -0,0 +1,27 @@
+use json_event_parser::{JsonValue, ReaderJsonParser, SliceJsonParser};
+
+fn nested_arrays(depth: usize) -> String {
+    format!("{}{}", "[".repeat(depth), "]".repeat(depth))
+}
+
+#[test]
+fn test_from_events_depth_limit() {
+    // Without configured limit, 128 nested arrays and objects are allowed
+    JsonValue::from_events(SliceJsonParser::new(nested_arrays(128).as_bytes())).unwrap();
+    JsonValue::from_events(SliceJsonParser::new(nested_arrays(129).as_bytes())).unwrap_err();
+
+    // The limit of the parser is used if set
+    let json = nested_arrays(1000);
+    JsonValue::from_events(SliceJsonParser::new(json.as_bytes()).with_max_stack_size(1000))
+        .unwrap();
+    JsonValue::from_events(ReaderJsonParser::new(json.as_bytes()).with_max_stack_size(1000))
+        .unwrap();
+    JsonValue::from_events(SliceJsonParser::new(b"[[[]]]").with_max_stack_size(2)).unwrap_err();
+    JsonValue::from_events(SliceJsonParser::new(b"[[]]").with_max_stack_size(2)).unwrap();
+}
+
+#[test]
+fn test_from_str_errors() {
+    assert!("[1] 2".parse::<JsonValue>().is_err());
+    assert!("{\"a\": 1".parse::<JsonValue>().is_err());
+}